@@ -0,0 +1,303 @@
+//! Finds impls of `#[dyncast]` traits that are missing the `#[dyncast]` attribute.
+//!
+//! Such an impl compiles fine, but the type is never registered and every cast to the trait
+//! silently returns `None`. The audit works on the source level: traits are matched by name, so an
+//! impl of `a::Boba` is reported if any scanned file declares a `#[dyncast] trait Boba`. Impls
+//! registered with `dyncast::register!(dyn Boba => A)` or `#[dyncast(Boba)] struct A;` are matched
+//! by trait name and self type.
+//! Items generated by macros aren't seen.
+
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use syn::{
+    parse::ParseStream, punctuated::Punctuated, visit::Visit, Attribute, Ident, ItemEnum, ItemImpl,
+    ItemStruct, ItemTrait, ItemUnion, Macro, Token, TraitBound, Type, TypeParamBound,
+};
+
+use crate::tokens::pretty;
+
+#[path = "../../impl/src/tokens.rs"]
+mod tokens;
+
+/// An impl of a dyncast trait without the `#[dyncast]` attribute.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Finding {
+    file: PathBuf,
+    line: usize,
+    column: usize,
+    trait_name: String,
+    self_ty: String,
+}
+
+impl Finding {
+    pub fn file(&self) -> &Path {
+        &self.file
+    }
+
+    /// The 1-based line of the `impl` keyword.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The 1-based column of the `impl` keyword.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The trait path as written in the impl.
+    pub fn trait_name(&self) -> &str {
+        &self.trait_name
+    }
+
+    /// The self type as written in the impl.
+    pub fn self_ty(&self) -> &str {
+        &self.self_ty
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: impl of `{}` for `{}` is missing #[dyncast]",
+            self.file.display(),
+            self.line,
+            self.column,
+            self.trait_name,
+            self.self_ty
+        )
+    }
+}
+
+/// The error returned when a source file can't be read or parsed.
+#[derive(Debug)]
+pub enum AuditError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, syn::Error),
+}
+
+impl fmt::Display for AuditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "failed to read `{}`: {err}", path.display()),
+            Self::Parse(path, err) => {
+                let start = err.span().start();
+                write!(
+                    f,
+                    "{}:{}:{}: failed to parse: {err}",
+                    path.display(),
+                    start.line,
+                    start.column + 1
+                )
+            }
+        }
+    }
+}
+
+impl Error for AuditError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(_, err) => Some(err),
+            Self::Parse(_, err) => Some(err),
+        }
+    }
+}
+
+/// Collects source files and reports unannotated impls across all of them.
+#[derive(Default)]
+pub struct Audit {
+    files: Vec<(PathBuf, syn::File)>,
+}
+
+impl Audit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a single source file, `path` is only used for reporting.
+    pub fn add_source(&mut self, path: impl Into<PathBuf>, source: &str) -> Result<(), AuditError> {
+        let path = path.into();
+        match syn::parse_file(source) {
+            Ok(file) => {
+                self.files.push((path, file));
+                Ok(())
+            }
+            Err(err) => Err(AuditError::Parse(path, err)),
+        }
+    }
+
+    /// Adds a `.rs` file or every `.rs` file below a directory, skipping `target` and hidden
+    /// directories.
+    pub fn add_path(&mut self, path: impl AsRef<Path>) -> Result<(), AuditError> {
+        let path = path.as_ref();
+        let io_err = |err| AuditError::Io(path.to_owned(), err);
+
+        if path.is_dir() {
+            let mut entries: Vec<PathBuf> = fs::read_dir(path)
+                .and_then(|entries| {
+                    entries
+                        .map(|entry| entry.map(|entry| entry.path()))
+                        .collect()
+                })
+                .map_err(io_err)?;
+            entries.sort_unstable();
+
+            for entry in entries {
+                let name = entry.file_name().unwrap_or_default().to_string_lossy();
+                if entry.is_dir() && (name == "target" || name.starts_with('.')) {
+                    continue;
+                }
+                if entry.is_dir() || name.ends_with(".rs") {
+                    self.add_path(&entry)?;
+                }
+            }
+            Ok(())
+        } else {
+            let source = fs::read_to_string(path).map_err(io_err)?;
+            self.add_source(path, &source)
+        }
+    }
+
+    /// Returns every unannotated impl of a dyncast trait, ordered by file and line.
+    pub fn findings(&self) -> Vec<Finding> {
+        let mut traits = TraitCollector::default();
+        for (_, file) in &self.files {
+            traits.visit_file(file);
+        }
+
+        let mut findings = Vec::new();
+        for (path, file) in &self.files {
+            let mut impls = ImplCollector {
+                traits: &traits.traits,
+                registered: &traits.registered,
+                path,
+                findings: &mut findings,
+            };
+            impls.visit_file(file);
+        }
+        findings.sort();
+        findings
+    }
+}
+
+fn is_dyncast_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "dyncast")
+}
+
+fn last_ident(path: &syn::Path) -> Option<String> {
+    path.segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+}
+
+/// Parses the input of `register!`, returning the trait name and the listed types.
+fn parse_register(input: ParseStream) -> syn::Result<(Option<String>, Vec<Type>)> {
+    let dyn_trait = match input.parse()? {
+        Type::TraitObject(dyn_trait) => dyn_trait,
+        ty => return Err(syn::Error::new_spanned(ty, "expected `dyn Trait`")),
+    };
+    input.parse::<Token![=>]>()?;
+    let types = Punctuated::<Type, Token![,]>::parse_terminated(input)?;
+
+    let trait_name = dyn_trait.bounds.iter().find_map(|bound| match bound {
+        TypeParamBound::Trait(bound) => last_ident(&bound.path),
+        _ => None,
+    });
+    Ok((trait_name, types.into_iter().collect()))
+}
+
+#[derive(Default)]
+struct TraitCollector {
+    traits: BTreeSet<String>,
+    /// `(trait name, self type)` pairs registered with `register!`.
+    registered: BTreeSet<(String, String)>,
+}
+
+impl TraitCollector {
+    /// Records the traits listed in `#[dyncast(Boba, ..)]` on a type definition.
+    fn register_type(&mut self, attrs: &[Attribute], ident: &Ident) {
+        for attr in attrs.iter().filter(|attr| is_dyncast_attr(attr)) {
+            let bounds =
+                attr.parse_args_with(Punctuated::<TraitBound, Token![,]>::parse_terminated);
+            for bound in bounds.into_iter().flatten() {
+                if let Some(trait_name) = last_ident(&bound.path) {
+                    self.registered.insert((trait_name, ident.to_string()));
+                }
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for TraitCollector {
+    fn visit_item_trait(&mut self, item: &'ast ItemTrait) {
+        if item.attrs.iter().any(is_dyncast_attr) {
+            self.traits.insert(item.ident.to_string());
+        }
+        syn::visit::visit_item_trait(self, item);
+    }
+
+    fn visit_item_struct(&mut self, item: &'ast ItemStruct) {
+        self.register_type(&item.attrs, &item.ident);
+        syn::visit::visit_item_struct(self, item);
+    }
+
+    fn visit_item_enum(&mut self, item: &'ast ItemEnum) {
+        self.register_type(&item.attrs, &item.ident);
+        syn::visit::visit_item_enum(self, item);
+    }
+
+    fn visit_item_union(&mut self, item: &'ast ItemUnion) {
+        self.register_type(&item.attrs, &item.ident);
+        syn::visit::visit_item_union(self, item);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        if last_ident(&mac.path).as_deref() == Some("register") {
+            if let Ok((Some(trait_name), types)) = mac.parse_body_with(parse_register) {
+                for ty in types {
+                    self.registered.insert((trait_name.clone(), pretty(&ty)));
+                }
+            }
+        }
+        syn::visit::visit_macro(self, mac);
+    }
+}
+
+struct ImplCollector<'a> {
+    traits: &'a BTreeSet<String>,
+    registered: &'a BTreeSet<(String, String)>,
+    path: &'a Path,
+    findings: &'a mut Vec<Finding>,
+}
+
+impl<'ast> Visit<'ast> for ImplCollector<'_> {
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        if let Some((None, trait_path, _)) = &item.trait_ {
+            let trait_name = last_ident(trait_path).unwrap_or_default();
+            let is_dyncast_trait = self.traits.contains(&trait_name);
+            let is_registered = self
+                .registered
+                .contains(&(trait_name, pretty(&item.self_ty)));
+
+            if is_dyncast_trait && !is_registered && !item.attrs.iter().any(is_dyncast_attr) {
+                let start = item.impl_token.span.start();
+                self.findings.push(Finding {
+                    file: self.path.to_owned(),
+                    line: start.line,
+                    column: start.column + 1,
+                    trait_name: pretty(trait_path),
+                    self_ty: pretty(&item.self_ty),
+                });
+            }
+        }
+        syn::visit::visit_item_impl(self, item);
+    }
+}
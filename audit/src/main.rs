@@ -0,0 +1,39 @@
+use std::{env, process::ExitCode};
+
+use dyncast_audit::Audit;
+
+const USAGE: &str = "usage: dyncast-audit [<path>...]
+
+Scans the given files and directories (default: `src`) for impls of #[dyncast]
+traits that are missing #[dyncast]. Exits with 1 if any were found.";
+
+fn main() -> ExitCode {
+    let mut paths = env::args().skip(1).collect::<Vec<_>>();
+    if paths.iter().any(|path| path.starts_with('-')) {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    }
+    if paths.is_empty() {
+        paths.push("src".to_owned());
+    }
+
+    let mut audit = Audit::new();
+    for path in &paths {
+        if let Err(err) = audit.add_path(path) {
+            eprintln!("error: {err}");
+            return ExitCode::from(2);
+        }
+    }
+
+    let findings = audit.findings();
+    for finding in &findings {
+        println!("{finding}");
+    }
+
+    if findings.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("{} unannotated impl(s) found", findings.len());
+        ExitCode::FAILURE
+    }
+}
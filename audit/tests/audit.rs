@@ -0,0 +1,108 @@
+use dyncast_audit::Audit;
+
+const TRAITS: &str = r#"
+use dyncast::dyncast;
+
+#[dyncast]
+pub trait Boba {}
+
+#[dyncast::dyncast]
+pub trait Soba<T> {}
+
+pub trait Plain {}
+"#;
+
+const IMPLS: &str = r#"
+use crate::traits::{Boba, Plain, Soba};
+
+struct A;
+struct B;
+
+#[dyncast]
+impl Boba for A {}
+
+impl Boba for B {}
+
+impl crate::traits::Soba<u8> for A {}
+
+impl Plain for A {}
+
+mod nested {
+    fn inner() {
+        struct C;
+        impl super::Boba for C {}
+    }
+}
+"#;
+
+#[test]
+fn unannotated_impls() {
+    let mut audit = Audit::new();
+    audit.add_source("src/traits.rs", TRAITS).unwrap();
+    audit.add_source("src/impls.rs", IMPLS).unwrap();
+
+    let findings = audit
+        .findings()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        findings,
+        [
+            "src/impls.rs:10:1: impl of `Boba` for `B` is missing #[dyncast]",
+            "src/impls.rs:12:1: impl of `crate::traits::Soba<u8>` for `A` is missing #[dyncast]",
+            "src/impls.rs:19:9: impl of `super::Boba` for `C` is missing #[dyncast]",
+        ]
+    );
+}
+
+#[test]
+fn negative_impls_and_other_traits() {
+    let mut audit = Audit::new();
+    audit
+        .add_source("lib.rs", "#[dyncast] trait Boba {}\nimpl Plain for u8 {}\n")
+        .unwrap();
+    assert!(audit.findings().is_empty());
+}
+
+#[test]
+fn parse_error() {
+    let err = Audit::new()
+        .add_source("broken.rs", "fn main( {}")
+        .unwrap_err();
+    assert!(err.to_string().starts_with("broken.rs:"));
+}
+
+#[test]
+fn registered_impls() {
+    let mut audit = Audit::new();
+    audit
+        .add_source(
+            "lib.rs",
+            r#"
+#[dyncast]
+trait Boba {}
+
+impl Boba for Vec<u8> {}
+impl Boba for Vec<u16> {}
+
+dyncast::register!(dyn Boba => Vec<u8>);
+
+#[dyncast(Display, Boba)]
+struct Taro;
+
+impl Boba for Taro {}
+"#,
+        )
+        .unwrap();
+
+    let findings = audit
+        .findings()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        findings,
+        ["lib.rs:6:1: impl of `Boba` for `Vec<u16>` is missing #[dyncast]"]
+    );
+}
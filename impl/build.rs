@@ -0,0 +1,25 @@
+use std::{env, process::Command};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(dyncast_diagnostic_namespace)");
+
+    // `#[diagnostic::on_unimplemented]` is stable since 1.78, the MSRV is lower. The attribute is
+    // emitted into the expanded code, which is compiled by the same compiler.
+    if rustc_minor_version().map_or(false, |minor| minor >= 78) {
+        println!("cargo:rustc-cfg=dyncast_diagnostic_namespace");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    // e.g. `rustc 1.78.0 (9b00956e5 2024-04-29)`
+    version
+        .strip_prefix("rustc 1.")?
+        .split('.')
+        .next()?
+        .parse()
+        .ok()
+}
@@ -1,10 +1,22 @@
+use proc_macro2::Span;
 use syn::{
+    parenthesized,
     parse::{Error, Parse, ParseStream},
-    Token,
+    punctuated::Punctuated,
+    Ident, Token, TraitBound, TraitBoundModifier, Type,
 };
 
-#[derive(Debug, Clone)]
-pub struct Args {}
+#[derive(Clone, Default)]
+pub struct Args {
+    /// `strict`: impls of the trait without `#[dyncast]` are rejected at compile time.
+    pub strict: Option<Span>,
+    /// `sealed`: only impls from the crate defining the trait are registered.
+    pub sealed: Option<Span>,
+    /// `instantiate(A, B, ..)`: the instantiations of a generic impl that get registered.
+    pub instantiate: Option<(Span, Vec<Type>)>,
+    /// `Display, Boba, ..`: the traits a struct or enum is registered for.
+    pub traits: Vec<TraitBound>,
+}
 
 fn parse_arg_list(
     input: ParseStream,
@@ -25,13 +37,62 @@ fn parse_arg_list(
     }
 }
 
+fn duplicate_arg(ident: &Ident) -> Error {
+    Error::new(ident.span(), format!("duplicate `{ident}` argument"))
+}
+
 impl Args {
     fn try_parse(input: ParseStream) -> Result<Self, Error> {
+        let mut args = Self::default();
+
         parse_arg_list(input, |input| {
-            Err(Error::new(input.span(), "unexpected argument"))
+            let is_flag = input.fork().parse::<Ident>().map_or(false, |ident| {
+                ident == "strict" || ident == "sealed" || ident == "instantiate"
+            });
+            if !is_flag {
+                let bound = input.parse::<TraitBound>()?;
+                if let TraitBoundModifier::Maybe(question) = bound.modifier {
+                    return Err(Error::new(question.span, "unexpected `?`"));
+                }
+                args.traits.push(bound);
+                return Ok(());
+            }
+
+            let ident = input.parse::<Ident>()?;
+
+            if ident == "instantiate" {
+                if args.instantiate.is_some() {
+                    return Err(duplicate_arg(&ident));
+                }
+
+                let content;
+                parenthesized!(content in input);
+                let types = Punctuated::<Type, Token![,]>::parse_terminated(&content)?;
+                if types.is_empty() {
+                    return Err(Error::new(
+                        ident.span(),
+                        "`instantiate` needs at least one type",
+                    ));
+                }
+
+                args.instantiate = Some((ident.span(), types.into_iter().collect()));
+                return Ok(());
+            }
+
+            let flag = if ident == "strict" {
+                &mut args.strict
+            } else {
+                &mut args.sealed
+            };
+
+            if flag.is_some() {
+                return Err(duplicate_arg(&ident));
+            }
+            *flag = Some(ident.span());
+            Ok(())
         })?;
 
-        Ok(Self {})
+        Ok(args)
     }
 }
 
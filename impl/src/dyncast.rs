@@ -1,38 +1,128 @@
-use proc_macro2::{Ident, Span, TokenStream};
-use quote::{quote, ToTokens};
+use std::collections::HashSet;
+
+use proc_macro2::{Group, Ident, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::{
-    parse_quote, spanned::Spanned, Error, GenericParam, Generics, ItemImpl, ItemTrait, Token,
-    TraitItem, WherePredicate,
+    parse_quote, punctuated::Punctuated, spanned::Spanned, ConstParam, DeriveInput, Error,
+    GenericArgument, GenericParam, Generics, ImplItem, ItemImpl, ItemTrait, Lifetime, Path,
+    PathArguments, Token, TraitBoundModifier, TraitItem, TypeParam, TypeParamBound, WherePredicate,
 };
 
-use crate::{args::Args, linker, parse::Item};
+use crate::{
+    args::Args,
+    instantiate::Bindings,
+    linker,
+    parse::{Item, RegisterTrait, Registrations},
+    tokens::tokens_to_string,
+};
 
+fn extract_generic_params_idents(generics: &Generics) -> TokenStream {
+    let mut ts = TokenStream::new();
+    for param in &generics.params {
+        match param {
+            GenericParam::Type(ty) => ty.ident.to_tokens(&mut ts),
+            GenericParam::Lifetime(lifetime) => lifetime.lifetime.to_tokens(&mut ts),
+            GenericParam::Const(konst) => konst.ident.to_tokens(&mut ts),
+        }
+        Token![,](Span::call_site()).to_tokens(&mut ts);
+    }
+    ts
+}
+
+/// Like [`extract_generic_params_idents`], without lifetimes.
 fn extract_generic_type_params_idents(generics: &Generics) -> TokenStream {
     let mut ts = TokenStream::new();
     for param in &generics.params {
-        if let GenericParam::Type(ty) = param {
-            ty.ident.to_tokens(&mut ts);
+        match param {
+            GenericParam::Type(ty) => ty.ident.to_tokens(&mut ts),
+            GenericParam::Const(konst) => konst.ident.to_tokens(&mut ts),
+            GenericParam::Lifetime(_) => continue,
         }
         Token![,](Span::call_site()).to_tokens(&mut ts);
     }
     ts
 }
 
-pub fn expand_trait(item: &mut ItemTrait, _args: Args) -> Result<TokenStream, Error> {
-    if let Some(first_const_param) = item.generics.const_params().next() {
-        return Err(Error::new(
-            first_const_param.span(),
-            "const generics aren't allowed for dyncastable traits",
-        ));
+/// Every non-empty combination of auto traits a `dyn Trait` can be extended with, together with
+/// the `AutoTraits` a registered type has to implement for it.
+fn auto_trait_combinations() -> Vec<(TokenStream, TokenStream)> {
+    let auto_traits = [
+        (
+            quote!(::std::marker::Send),
+            quote!(::dyncast::private::AutoTraits::SEND),
+        ),
+        (
+            quote!(::std::marker::Sync),
+            quote!(::dyncast::private::AutoTraits::SYNC),
+        ),
+        (
+            quote!(::std::marker::Unpin),
+            quote!(::dyncast::private::AutoTraits::UNPIN),
+        ),
+    ];
+
+    (1..1u8 << auto_traits.len())
+        .map(|mask| {
+            let selected = auto_traits
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| mask & (1 << idx) != 0)
+                .map(|(_, auto_trait)| auto_trait);
+            let bounds = selected.clone().map(|(bound, _)| bound);
+            let flags = selected.map(|(_, flag)| flag);
+            (
+                quote!(#(+ #bounds)*),
+                quote!(::dyncast::private::AutoTraits::NONE #(.union(#flags))*),
+            )
+        })
+        .collect()
+}
+
+fn reject_traits(args: &Args) -> Result<(), Error> {
+    match args.traits.first() {
+        Some(bound) => Err(Error::new(bound.span(), "unexpected argument")),
+        None => Ok(()),
     }
+}
 
-    if let Some(first_lifetime_param) = item.generics.lifetimes().next() {
+pub fn expand_trait(item: &mut ItemTrait, args: Args) -> Result<TokenStream, Error> {
+    expand_trait_at(item, args, None)
+}
+
+/// Expands `item`, or only the impls for a trait that can't be annotated if its path is given. In
+/// that case `item` is a stand-in declaring the trait's name and supertraits.
+fn expand_trait_at(
+    item: &mut ItemTrait,
+    args: Args,
+    foreign_path: Option<&Path>,
+) -> Result<TokenStream, Error> {
+    reject_traits(&args)?;
+    if let Some((span, _)) = args.instantiate {
         return Err(Error::new(
-            first_lifetime_param.span(),
-            "lifetime generics aren't allowed for dyncastable traits",
+            span,
+            "`instantiate` is only allowed on generic trait impls",
         ));
     }
 
+    // Associated types are bound by the trait object, e.g. `dyn Source<Item = __Item>`. Every
+    // binding is its own registration key.
+    let mut assoc_types = Vec::new();
+    for trait_item in &item.items {
+        if let TraitItem::Type(assoc_type) = trait_item {
+            if !assoc_type.generics.params.is_empty() {
+                return Err(Error::new(
+                    assoc_type.generics.params.span(),
+                    "generic associated types aren't allowed for dyncastable traits",
+                ));
+            }
+            assoc_types.push(assoc_type.ident.clone());
+        }
+    }
+    let assoc_params = assoc_types
+        .iter()
+        .map(|assoc_type| format_ident!("__{}", assoc_type))
+        .collect::<Vec<_>>();
+
     let predicates = item
         .generics
         .type_params()
@@ -49,145 +139,453 @@ pub fn expand_trait(item: &mut ItemTrait, _args: Args) -> Result<TokenStream, Er
         .predicates
         .extend(predicates);
 
-    let trait_ident = &item.ident;
+    let trait_path = match foreign_path {
+        Some(path) => path.to_token_stream(),
+        None => item.ident.to_token_stream(),
+    };
     let generics_lt = &item.generics.lt_token;
-    let generics_params = &item.generics.params;
     let generics_gt = &item.generics.gt_token;
     let generics_where = &item.generics.where_clause;
+    let generics_where_predicates = generics_where
+        .iter()
+        .flat_map(|where_clause| &where_clause.predicates)
+        .collect::<Vec<_>>();
+    let generated_where = quote! {
+        where
+            #(#generics_where_predicates,)*
+            #(#assoc_params: 'static,)*
+    };
 
-    let generics_params_pass = extract_generic_type_params_idents(&item.generics);
+    // Lifetime parameters are bound by a `for<..>` binder, everything generated for the trait is
+    // only generic over its type and const parameters. E.g. `trait Visitor<'a, T>` is registered
+    // and cast to as `dyn for<'a> Visitor<'a, T>`. Defaults aren't allowed on impls, they only
+    // matter for naming the trait (`dyn Codec` for `trait Codec<T = Vec<u8>>`).
+    let provider_generics_params = item
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(ty) => Some(GenericParam::Type(TypeParam {
+                eq_token: None,
+                default: None,
+                ..ty.clone()
+            })),
+            GenericParam::Const(konst) => Some(GenericParam::Const(ConstParam {
+                eq_token: None,
+                default: None,
+                ..konst.clone()
+            })),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect::<Punctuated<GenericParam, Token![,]>>();
+    // The trait object impls are additionally generic over the associated type bindings.
+    let generics_params = provider_generics_params
+        .iter()
+        .cloned()
+        .chain(
+            assoc_params
+                .iter()
+                .map(|assoc_param| GenericParam::Type(parse_quote!(#assoc_param))),
+        )
+        .collect::<Punctuated<GenericParam, Token![,]>>();
+    let lifetimes = item
+        .generics
+        .lifetimes()
+        .map(|lifetime_param| &lifetime_param.lifetime)
+        .collect::<Vec<_>>();
+    let higher_ranked = if lifetimes.is_empty() {
+        quote!()
+    } else {
+        quote!(for<#(#lifetimes),*>)
+    };
+
+    let generics_params_pass = extract_generic_params_idents(&item.generics);
+    let generic_type_params_pass = extract_generic_type_params_idents(&item.generics);
 
     let trait_ident_with_params = quote!(
-        #trait_ident #generics_lt #generics_params_pass #generics_gt
+        #trait_path #generics_lt #generics_params_pass #generics_gt
+    );
+    let trait_ident_with_bindings = if assoc_types.is_empty() {
+        trait_ident_with_params.clone()
+    } else {
+        quote!(#trait_path<#generics_params_pass #(#assoc_types = #assoc_params),*>)
+    };
+    let trait_bound = quote!(#higher_ranked #trait_ident_with_bindings);
+    let dyn_trait = quote!(dyn #trait_bound);
+
+    // The provider isn't generic over the associated types, a `Provider<<Self as Trait>::Item>`
+    // supertrait would make the trait dyn incompatible. Its blanket impl binds them to the ones of
+    // the implementor instead. They can't depend on the trait's lifetimes for the trait object to
+    // be nameable, so they're projected from the `'static` instantiation.
+    let static_lifetimes = lifetimes.iter().map(|_| quote!('static));
+    let trait_ident_static = quote!(
+        #trait_path<#(#static_lifetimes,)* #generic_type_params_pass>
     );
+    let trait_bound_for_t = quote!(#higher_ranked #trait_ident_with_params);
+    let dyn_trait_for_t = if assoc_types.is_empty() {
+        dyn_trait.clone()
+    } else {
+        quote!(
+            dyn #higher_ranked #trait_path<
+                #generics_params_pass
+                #(#assoc_types = <__T as #trait_ident_static>::#assoc_types),*
+            >
+        )
+    };
 
     let dyncast_provider = format!("{}DyncastProvider", &item.ident);
     let dyncast_provider = Ident::new(
         &dyncast_provider,
         Span::call_site().located_at(item.ident.span()),
     );
-    let dyncast_provider_with_params =
-        quote!(#dyncast_provider #generics_lt #generics_params_pass #generics_gt);
+    let dyncast_provider_with_params = quote!(#dyncast_provider<#generic_type_params_pass>);
+    // In strict mode the provider is a supertrait of the trait and has to be as visible, otherwise
+    // it stays private.
+    let dyncast_provider_vis = if args.strict.is_some() {
+        item.vis.to_token_stream()
+    } else {
+        quote!()
+    };
 
-    let dyncast_descriptor_ref = quote! {
-        #[doc(hidden)]
-        unsafe fn __dyncast_descriptor_ref() -> ::dyncast::private::Descriptor
-        where
-            Self: 'static + ::std::marker::Sized + #dyncast_provider_with_params
-        {
-            <Self as #dyncast_provider_with_params>::dyncast_descriptor()
+    // Whether a supertrait is a dyncast trait can't be told from its path, that's left to
+    // `probe::Supertrait`. Lifetimes of the trait are bound like they are for the trait itself.
+    let dyn_supertraits = item
+        .supertraits
+        .iter()
+        .filter_map(|bound| match bound {
+            TypeParamBound::Trait(bound)
+                if matches!(bound.modifier, TraitBoundModifier::None)
+                    && !bound
+                        .path
+                        .segments
+                        .last()
+                        .map_or(false, |segment| segment.ident == "Sized") =>
+            {
+                Some(bound)
+            }
+            _ => None,
+        })
+        .map(|bound| {
+            let path = &bound.path;
+            let mut bound_lifetimes = lifetimes
+                .iter()
+                .filter(|lifetime| mentions_lifetime(path.to_token_stream(), lifetime))
+                .map(|lifetime| quote!(#lifetime))
+                .collect::<Vec<_>>();
+            if let Some(own_lifetimes) = &bound.lifetimes {
+                bound_lifetimes.extend(
+                    own_lifetimes
+                        .lifetimes
+                        .iter()
+                        .map(ToTokens::to_token_stream),
+                );
+            }
+            if bound_lifetimes.is_empty() {
+                quote!(dyn #path)
+            } else {
+                quote!(dyn for<#(#bound_lifetimes),*> #path)
+            }
+        })
+        .collect::<Vec<_>>();
+    let (supertrait_descriptors, supertrait_infos) = if dyn_supertraits.is_empty() {
+        (quote!(), quote!(::std::vec::Vec::new()))
+    } else {
+        (
+            quote! {
+                .with_supertraits(|| {
+                    #[allow(unused_imports)]
+                    use ::dyncast::private::probe::NotDyncast as _;
+
+                    [#(::dyncast::private::probe::Supertrait::<#dyn_supertraits, __T>::DESCRIPTOR),*]
+                        .into_iter()
+                        .flatten()
+                        .map(|descriptor| descriptor())
+                        .collect()
+                })
+            },
+            quote! {{
+                #[allow(unused_imports)]
+                use ::dyncast::private::probe::NotDyncast as _;
+
+                [#(::dyncast::private::probe::Supertrait::<#dyn_supertraits>::TRAIT_INFO),*]
+                    .into_iter()
+                    .flatten()
+                    .map(|trait_info| trait_info())
+                    .collect()
+            }},
+        )
+    };
+
+    item.colon_token.get_or_insert_with(Default::default);
+    item.supertraits
+        .push(parse_quote!(::dyncast::private::SourceHook));
+
+    // In strict mode the provider becomes a supertrait and is only implemented for types that have
+    // been registered by `expand_impl`.
+    let dyncast_provider_registered = if args.strict.is_some() {
+        item.supertraits
+            .push(parse_quote!(#dyncast_provider_with_params));
+        quote!(#dyn_trait_for_t: ::dyncast::private::Registered<__T>,)
+    } else {
+        quote!()
+    };
+    // The unsatisfied provider bound is what's reported for an impl without `#[dyncast]`.
+    let dyncast_provider_diagnostic = if cfg!(dyncast_diagnostic_namespace) && args.strict.is_some()
+    {
+        let message = format!(
+            "the impl of `{}` for `{{Self}}` is missing #[dyncast]",
+            item.ident
+        );
+        quote! {
+            #[diagnostic::on_unimplemented(
+                message = #message,
+                label = "missing #[dyncast]",
+                note = "traits declared with #[dyncast(strict)] only accept impls annotated with #[dyncast]",
+            )]
         }
+    } else {
+        quote!()
+    };
+
+    // Type arguments are recorded by name, const arguments by their value.
+    let generic_args = item.generics.params.iter().filter_map(|param| match param {
+        GenericParam::Type(TypeParam { ident, .. }) => Some(quote! {
+            ::std::borrow::Cow::Borrowed(::std::any::type_name::<#ident>())
+        }),
+        GenericParam::Const(ConstParam { ident, .. }) => Some(quote! {
+            ::std::borrow::Cow::Owned(::std::string::ToString::to_string(&#ident))
+        }),
+        GenericParam::Lifetime(_) => None,
+    });
+
+    // A trait is sealed as well if one of its supertraits is. The supertraits are checked to be
+    // defined in the same crate, otherwise the registrations of the trait would register types of
+    // other crates for them. Supertraits naming parameters of the trait can't be checked here.
+    let sealed = args.sealed.is_some();
+    let sealed = if dyn_supertraits.is_empty() {
+        quote!(#sealed)
+    } else {
+        quote! {{
+            #[allow(unused_imports)]
+            use ::dyncast::private::probe::NotDyncast as _;
+
+            #sealed #(|| ::dyncast::private::probe::Supertrait::<#dyn_supertraits>::SEALED)*
+        }}
     };
-    let dyncast_descriptor_ref = syn::parse2::<TraitItem>(dyncast_descriptor_ref).unwrap();
+    let generic_idents = item
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .chain(item.generics.const_params().map(|param| &param.ident))
+        .collect::<Vec<_>>();
+    let local_supertraits = dyn_supertraits.iter().filter(|dyn_supertrait| {
+        !generic_idents
+            .iter()
+            .any(|ident| mentions_ident((*dyn_supertrait).clone(), ident))
+    });
 
-    item.items.push(dyncast_descriptor_ref);
+    let (auto_trait_bounds, auto_trait_flags): (Vec<_>, Vec<_>) =
+        auto_trait_combinations().into_iter().unzip();
+
+    // Inherent impls aren't allowed on the trait objects of other crates' traits. Traits registered
+    // through `register_trait!` are never sealed, so they don't need it.
+    let dyncast_local = if foreign_path.is_none() {
+        quote! {
+            impl <#generics_params> #dyn_trait
+            #generated_where
+            {
+                #[doc(hidden)]
+                #[allow(dead_code)]
+                pub(crate) const DYNCAST_LOCAL: bool = true;
+            }
+        }
+    } else {
+        quote!()
+    };
 
     Ok(quote! {
         /// # Safety
         /// This trait must *not* be implemented on any type manually. Doing so might cause UB.
         #[doc(hidden)]
-        unsafe trait #dyncast_provider #generics_lt #generics_params #generics_gt : #trait_ident_with_params
+        #dyncast_provider_diagnostic
+        #dyncast_provider_vis unsafe trait #dyncast_provider <#provider_generics_params>
         #generics_where
+        {
+            fn dyncast_descriptor() -> ::dyncast::private::Descriptor
+            where
+                Self: 'static + ::std::marker::Sized;
+        }
+
+        // The descriptor is built here rather than in a default method of the trait, proving a
+        // higher-ranked bound there would be ambiguous with the trait's own `Self: Trait<'a>`.
+        unsafe impl<__T, #provider_generics_params> #dyncast_provider_with_params for __T
+        where
+            __T: #trait_bound_for_t,
+            #dyncast_provider_registered
+            #(#generics_where_predicates,)*
+            #(<__T as #trait_ident_static>::#assoc_types: 'static,)*
         {
             #[inline(always)]
             fn dyncast_descriptor() -> ::dyncast::private::Descriptor
             where
-                Self: 'static + ::std::marker::Sized,
+                Self: 'static,
             {
                 unsafe {
                     ::dyncast::private::Descriptor::new(
                         ::std::any::TypeId::of::<Self>(),
-                        ::std::any::TypeId::of::<dyn #trait_ident_with_params>(),
-                        Self::dyncast_attach_vtable
+                        ::std::any::type_name::<Self>(),
+                        ::std::any::TypeId::of::<#dyn_trait_for_t>(),
+                        <#dyn_trait_for_t as ::dyncast::private::Dyncast>::dyncast_trait_info,
+                        ::dyncast::private::ptr::metadata(
+                            ::std::ptr::null::<Self>() as *const #dyn_trait_for_t
+                        ),
                     )
+                    #supertrait_descriptors
                 }
             }
+        }
 
-            fn dyncast_attach_vtable(ptr: *const ()) -> *const dyn #trait_ident_with_params
-            where
-                Self: 'static + ::std::marker::Sized,
-            {
-                unsafe {
-                    let vtable = ::dyncast::private::ptr::metadata(
-                        ::std::ptr::null::<Self>() as *const dyn #trait_ident_with_params
-                    );
-                    ::dyncast::private::ptr::from_raw_parts(ptr, vtable)
-                }
+        unsafe impl<__T, #generics_params> ::dyncast::private::DescriptorOf<__T> for #dyn_trait
+        where
+            __T: 'static + #trait_bound,
+            #(#generics_where_predicates,)*
+            #(#assoc_params: 'static,)*
+        {
+            #[inline]
+            fn descriptor() -> ::dyncast::private::Descriptor {
+                <__T as #dyncast_provider_with_params>::dyncast_descriptor()
             }
         }
 
-        unsafe impl<__T: #trait_ident_with_params, #generics_params>
-            #dyncast_provider_with_params for __T
-            #generics_where
-        {}
+        #dyncast_local
 
-        impl #generics_lt #generics_params #generics_gt ::dyncast::private::Dyncast
-        for dyn #trait_ident_with_params
-        #generics_where
+        #(
+            const _: () = {
+                #[allow(unused_imports)]
+                use ::dyncast::private::probe::{Foreign as _, NotDyncast as _};
+
+                ::std::assert!(
+                    <#local_supertraits>::DYNCAST_LOCAL
+                        || !::dyncast::private::probe::Supertrait::<#local_supertraits>::SEALED,
+                    "dyncast traits with a sealed supertrait have to be defined in its crate"
+                );
+            };
+        )*
+
+        unsafe impl <#generics_params> ::dyncast::private::Dyncast
+        for #dyn_trait
+        #generated_where
         {
-            fn dyncast_from<__T: ?::std::marker::Sized + ::std::any::Any>(
-                __source: &__T
-            ) -> ::std::option::Option<&Self> {
-                use ::std::any::Any;
+            const SEALED: bool = #sealed;
 
+            fn dyncast_descriptor(
+                __self_type_id: ::std::any::TypeId
+            ) -> ::std::option::Option<::dyncast::private::PartialDescriptor> {
                 let __map = unsafe {
                     ::dyncast::private::LazyTypeMap::<
-                        dyn #trait_ident_with_params
+                        #dyn_trait
                     >::current().get_or_init()
                 };
 
-                let __descriptor = __map.get(::std::any::Any::type_id(__source))?;
+                __map.get(__self_type_id).copied()
+            }
 
-                Some(unsafe {
-                    &*(__descriptor.attach_vtable_fn())(__source as *const _ as *const ())
-                })
+            fn dyncast_trait_info() -> ::dyncast::private::TraitInfo {
+                ::dyncast::private::trait_info(
+                    ::std::any::TypeId::of::<#dyn_trait>(),
+                    ::std::any::type_name::<#dyn_trait>(),
+                    ::std::module_path!(),
+                    ::std::file!(),
+                    ::std::line!(),
+                    ::std::vec![#(#generic_args),*],
+                    <Self as ::dyncast::private::Dyncast>::SEALED,
+                    #supertrait_infos,
+                )
             }
         }
-    })
-}
 
-pub fn expand_impl(item: &ItemImpl, _args: Args) -> Result<TokenStream, Error> {
-    if let Some(span) = item
-        .generics
-        .const_params()
-        .next()
-        .map(|first_const_param| first_const_param.span())
-        .or_else(|| {
-            item.generics
-                .type_params()
-                .next()
-                .map(|first_type_param| first_type_param.span())
-        })
-        .or_else(|| {
-            item.generics
-                .lifetimes()
-                .next()
-                .map(|first_lifetime_param| first_lifetime_param.span())
-        })
-    {
-        return Err(Error::new(
-            span.span(),
-            "generics aren't allowed for dyncastable trait impls",
-        ));
-    }
+        #(
+            unsafe impl <#generics_params> ::dyncast::private::Dyncast
+            for #dyn_trait #auto_trait_bounds
+            #generated_where
+            {
+                #[inline]
+                fn dyncast_descriptor(
+                    __self_type_id: ::std::any::TypeId
+                ) -> ::std::option::Option<::dyncast::private::PartialDescriptor> {
+                    <#dyn_trait as ::dyncast::private::Dyncast>::dyncast_descriptor(
+                        __self_type_id
+                    )
+                    .filter(|__descriptor| __descriptor.auto_traits().contains(#auto_trait_flags))
+                }
 
-    let trait_path = match &item.trait_ {
-        Some((None, path, _)) => path,
-        _ => {
-            return Err(Error::new(
-                item.impl_token.span,
-                "inherent impls are invalid here",
-            ))
-        }
-    };
-    let self_ty = &*item.self_ty;
+                #[inline]
+                fn dyncast_trait_id() -> ::std::any::TypeId {
+                    ::std::any::TypeId::of::<#dyn_trait>()
+                }
+
+                fn dyncast_trait_info() -> ::dyncast::private::TraitInfo {
+                    <#dyn_trait as ::dyncast::private::Dyncast>::dyncast_trait_info()
+                }
+            }
+        )*
+    })
+}
 
+/// Registers `self_ty` for `dyn #trait_bound`.
+fn expand_registration(self_ty: &TokenStream, trait_bound: &TokenStream) -> TokenStream {
     let elf_section = linker::elf::SECTION;
     let macho_section = linker::macho::SECTION;
     let windows_section = linker::windows::SECTION;
 
-    Ok(quote! {
+    let dyn_trait = quote!(dyn #trait_bound);
+    // The only bound the self type has to satisfy is the trait, so that an unsatisfied
+    // instantiation is reported at the type argument as not implementing it.
+    let descriptor = quote_spanned! {self_ty.span()=>
+        __dyncast_descriptor::<#self_ty>()
+    };
+
+    quote! {
         const _: () = {
+            #[allow(unused_imports)]
+            use ::dyncast::private::probe::Foreign as _;
+
+            ::std::assert!(
+                <#dyn_trait>::DYNCAST_LOCAL
+                    || !<#dyn_trait as ::dyncast::private::Dyncast>::SEALED,
+                "sealed dyncast traits only accept registrations from the crate defining them"
+            );
+
+            struct __DyncastRegistration;
+
+            fn __dyncast_descriptor<__T: 'static + #trait_bound>() -> ::dyncast::private::Descriptor {
+                <#dyn_trait as ::dyncast::private::DescriptorOf<__T>>::descriptor()
+            }
+
+            unsafe impl ::dyncast::private::Registration for __DyncastRegistration {
+                const AUTO_TRAITS: ::dyncast::private::AutoTraits = {
+                    #[allow(unused_imports)]
+                    use ::dyncast::private::probe::{NotSend as _, NotSync as _, NotUnpin as _};
+
+                    ::dyncast::private::AutoTraits::probe(
+                        ::dyncast::private::probe::Probe::<#self_ty>::SEND,
+                        ::dyncast::private::probe::Probe::<#self_ty>::SYNC,
+                        ::dyncast::private::probe::Probe::<#self_ty>::UNPIN,
+                    )
+                };
+                const MODULE_PATH: &'static str = ::std::module_path!();
+                const FILE: &'static str = ::std::file!();
+                const LINE: u32 = ::std::line!();
+            }
+
+            unsafe impl ::dyncast::private::RegistrationEntry<#dyn_trait, __DyncastRegistration>
+            for #self_ty
+            {
+                fn descriptor() -> ::dyncast::private::Descriptor {
+                    #descriptor.with_registration::<__DyncastRegistration>()
+                }
+            }
+
             #[cfg_attr(
                 any(target_os = "macos", target_os = "ios", target_os = "tvos"),
                 link_section = #macho_section
@@ -202,15 +600,339 @@ pub fn expand_impl(item: &ItemImpl, _args: Args) -> Result<TokenStream, Error> {
             )]
             #[used]
             static REF_DYNCAST: ::dyncast::private::Entry = ::dyncast::private::Entry::new(
-                <#self_ty as #trait_path>::__dyncast_descriptor_ref
+                <#self_ty as ::dyncast::private::RegistrationEntry<
+                    #dyn_trait,
+                    __DyncastRegistration
+                >>::descriptor
             );
         };
+    }
+}
+
+/// Replaces the lifetime arguments of the trait path, e.g. `Visitor<'a>`.
+fn map_trait_lifetimes(trait_path: &Path, mut f: impl FnMut() -> Lifetime) -> Path {
+    let mut trait_path = trait_path.clone();
+    if let Some(PathArguments::AngleBracketed(args)) = trait_path
+        .segments
+        .last_mut()
+        .map(|segment| &mut segment.arguments)
+    {
+        for arg in &mut args.args {
+            if let GenericArgument::Lifetime(lifetime) = arg {
+                *lifetime = f();
+            }
+        }
+    }
+    trait_path
+}
+
+/// The bound of the `dyn Trait` an impl of `trait_path` is registered for. Traits with lifetime
+/// parameters are registered as `dyn for<'a> Trait<'a>`, no matter which lifetimes the impl names.
+/// Associated types are bound to the types of the impl, e.g. `dyn Source<Item = u32>`.
+fn registered_trait_bound(
+    item: &ItemImpl,
+    trait_path: &Path,
+    self_ty: &TokenStream,
+    bindings: Option<&Bindings>,
+) -> Result<TokenStream, Error> {
+    let mut lifetimes = Vec::new();
+    let mut trait_path = map_trait_lifetimes(trait_path, || {
+        let lifetime = Lifetime::new(
+            &format!("'__dyncast_{}", lifetimes.len()),
+            Span::call_site(),
+        );
+        lifetimes.push(lifetime.clone());
+        lifetime
+    });
+
+    for impl_item in &item.items {
+        if let ImplItem::Type(assoc_type) = impl_item {
+            let mut ty = replace_self(assoc_type.ty.to_token_stream(), self_ty);
+            if let Some(bindings) = bindings {
+                ty = bindings.substitute(ty);
+            }
+            let ident = &assoc_type.ident;
+            let binding: GenericArgument = syn::parse2(quote!(#ident = #ty))?;
+
+            let last_segment = trait_path.segments.last_mut().unwrap();
+            if let PathArguments::None = last_segment.arguments {
+                last_segment.arguments = PathArguments::AngleBracketed(parse_quote!(<>));
+            }
+            if let PathArguments::AngleBracketed(args) = &mut last_segment.arguments {
+                args.args.push(binding);
+            }
+        }
+    }
+
+    if lifetimes.is_empty() {
+        Ok(quote!(#trait_path))
+    } else {
+        Ok(quote!(for<#(#lifetimes),*> #trait_path))
+    }
+}
+
+/// `Self` isn't allowed in the header of the generated impls, replace it with the self type.
+fn replace_self(tokens: TokenStream, self_ty: &TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .flat_map(|tt| match tt {
+            TokenTree::Group(group) => {
+                let mut replaced =
+                    Group::new(group.delimiter(), replace_self(group.stream(), self_ty));
+                replaced.set_span(group.span());
+                TokenStream::from(TokenTree::Group(replaced))
+            }
+            TokenTree::Ident(ident) if ident == "Self" => self_ty.clone(),
+            tt => TokenStream::from(tt),
+        })
+        .collect()
+}
+
+fn mentions_ident(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Group(group) => mentions_ident(group.stream(), ident),
+        TokenTree::Ident(other) => other == *ident,
+        _ => false,
+    })
+}
+
+fn mentions_lifetime(tokens: TokenStream, lifetime: &Lifetime) -> bool {
+    let mut after_quote = false;
+    for tt in tokens {
+        let found = match &tt {
+            TokenTree::Group(group) => mentions_lifetime(group.stream(), lifetime),
+            TokenTree::Ident(ident) => after_quote && *ident == lifetime.ident,
+            _ => false,
+        };
+        if found {
+            return true;
+        }
+        after_quote = matches!(&tt, TokenTree::Punct(punct) if punct.as_char() == '\'');
+    }
+    false
+}
+
+fn expand_non_generic_impl(item: &ItemImpl, trait_path: &Path) -> Result<TokenStream, Error> {
+    let self_ty = &*item.self_ty;
+
+    // Lifetime parameters that only appear in the trait path, like in
+    // `impl<'a> Visitor<'a> for Printer`, don't need instantiations.
+    if let Some(first_param) = item.generics.params.iter().find(|param| match param {
+        GenericParam::Lifetime(param) => {
+            mentions_lifetime(self_ty.to_token_stream(), &param.lifetime)
+        }
+        _ => true,
+    }) {
+        return Err(Error::new(
+            first_param.span(),
+            "generic dyncastable trait impls need to list the registered instantiations, \
+             e.g. `#[dyncast(instantiate(Wrapper<u8>))]`",
+        ));
+    }
+
+    let trait_bound = registered_trait_bound(item, trait_path, &self_ty.to_token_stream(), None)?;
+    let registration = expand_registration(&self_ty.to_token_stream(), &trait_bound);
+
+    Ok(quote! {
+        const _: () = {
+            unsafe impl ::dyncast::private::Registered<#self_ty> for dyn #trait_bound {}
+        };
+
+        #registration
+    })
+}
+
+pub fn expand_impl(item: &ItemImpl, args: Args) -> Result<TokenStream, Error> {
+    reject_traits(&args)?;
+    if let Some((span, name)) = args
+        .strict
+        .map(|span| (span, "strict"))
+        .or(args.sealed.map(|span| (span, "sealed")))
+    {
+        return Err(Error::new(
+            span,
+            format!("`{name}` is only allowed on dyncastable traits"),
+        ));
+    }
+
+    let trait_path = match &item.trait_ {
+        Some((None, path, _)) => path,
+        _ => {
+            return Err(Error::new(
+                item.impl_token.span,
+                "inherent impls are invalid here",
+            ))
+        }
+    };
+    let self_ty = &*item.self_ty;
+
+    let (instantiate_span, instantiations) = match args.instantiate {
+        Some(instantiate) => instantiate,
+        None => return expand_non_generic_impl(item, trait_path),
+    };
+
+    if item.generics.params.is_empty() {
+        return Err(Error::new(
+            instantiate_span,
+            "`instantiate` is only allowed on generic impls",
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    let mut registrations = Vec::new();
+
+    for instance in &instantiations {
+        if !seen.insert(tokens_to_string(instance)) {
+            return Err(Error::new(instance.span(), "duplicate instantiation"));
+        }
+
+        let bindings = Bindings::unify(&item.generics, self_ty, instance)?;
+        let trait_path = syn::parse2::<Path>(bindings.substitute(trait_path.to_token_stream()))?;
+        let trait_bound = registered_trait_bound(
+            item,
+            &trait_path,
+            &instance.to_token_stream(),
+            Some(&bindings),
+        )?;
+
+        registrations.push(expand_registration(
+            &instance.to_token_stream(),
+            &trait_bound,
+        ));
+    }
+
+    // `Registered` is implemented for the whole impl, including blanket impls, so that strict
+    // traits accept it.
+    let trait_bound = registered_trait_bound(item, trait_path, &self_ty.to_token_stream(), None)?;
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+    let registered = quote! {
+        unsafe impl #impl_generics ::dyncast::private::Registered<#self_ty>
+        for dyn #trait_bound #where_clause {}
+    };
+
+    Ok(quote! {
+        const _: () = {
+            #registered
+        };
+
+        #(#registrations)*
     })
 }
 
+/// Registers a struct, enum or union for every listed trait, as if each impl was annotated.
+pub fn expand_type(item: &DeriveInput, args: Args) -> Result<TokenStream, Error> {
+    if let Some((span, name)) = args
+        .strict
+        .map(|span| (span, "strict"))
+        .or(args.sealed.map(|span| (span, "sealed")))
+    {
+        return Err(Error::new(
+            span,
+            format!("`{name}` is only allowed on dyncastable traits"),
+        ));
+    }
+    if let Some((span, _)) = args.instantiate {
+        return Err(Error::new(
+            span,
+            "`instantiate` is only allowed on generic trait impls",
+        ));
+    }
+    if args.traits.is_empty() {
+        return Err(Error::new(
+            item.ident.span(),
+            "expected the traits to register the type for, e.g. `#[dyncast(Display)]`",
+        ));
+    }
+    if !item.generics.params.is_empty() {
+        return Err(Error::new(
+            item.generics.params.span(),
+            "generic types can't be registered at their definition, \
+             use `dyncast::register!` with the instantiations instead",
+        ));
+    }
+
+    let self_ty = item.ident.to_token_stream();
+    let mut seen = HashSet::new();
+    let mut expanded = TokenStream::new();
+    for bound in &args.traits {
+        if !seen.insert(tokens_to_string(bound)) {
+            return Err(Error::new(bound.span(), "duplicate trait"));
+        }
+
+        let registration = expand_registration(&self_ty, &bound.to_token_stream());
+        expanded.extend(quote! {
+            const _: () = {
+                unsafe impl ::dyncast::private::Registered<#self_ty> for dyn #bound {}
+            };
+
+            #registration
+        });
+    }
+
+    Ok(expanded)
+}
+
 pub fn expand(item: &mut Item, args: Args) -> Result<TokenStream, Error> {
     match item {
         Item::Trait(item) => expand_trait(item, args),
         Item::Impl(item) => expand_impl(item, args),
+        Item::Type(item) => expand_type(item, args),
+    }
+}
+
+pub fn expand_register(registrations: &Registrations) -> Result<TokenStream, Error> {
+    let dyn_trait = &registrations.dyn_trait;
+    if dyn_trait.bounds.len() != 1 || !matches!(dyn_trait.bounds[0], TypeParamBound::Trait(_)) {
+        return Err(Error::new(
+            dyn_trait.bounds.span(),
+            "expected a single trait, auto traits are detected for every registered type",
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    let mut expanded = TokenStream::new();
+    for ty in &registrations.types {
+        if !seen.insert(tokens_to_string(ty)) {
+            return Err(Error::new(ty.span(), "duplicate type"));
+        }
+        expanded.extend(expand_registration(
+            &ty.to_token_stream(),
+            &dyn_trait.bounds.to_token_stream(),
+        ));
+    }
+
+    Ok(expanded)
+}
+
+/// Makes a trait that can't be annotated a cast target, see `register_trait!`.
+pub fn expand_register_trait(input: &RegisterTrait) -> Result<TokenStream, Error> {
+    match input {
+        RegisterTrait::Dyn {
+            trait_path,
+            supertraits,
+        } => {
+            let ident = match trait_path.segments.last() {
+                Some(segment) => &segment.ident,
+                None => return Err(Error::new(trait_path.span(), "expected a trait")),
+            };
+            let mut item: ItemTrait = parse_quote!(trait #ident: #(#supertraits)+* {});
+            let expanded = expand_trait_at(&mut item, Args::default(), Some(trait_path))?;
+
+            Ok(quote! {
+                const _: () = {
+                    #expanded
+                };
+            })
+        }
+        RegisterTrait::Local {
+            vis,
+            ident,
+            supertrait,
+        } => Ok(quote! {
+            #[::dyncast::dyncast]
+            #vis trait #ident: #supertrait {}
+
+            impl<__T: #supertrait> #ident for __T {}
+        }),
     }
 }
@@ -0,0 +1,287 @@
+//! Syntactic matching of `instantiate(...)` types against the self type of a generic impl.
+//!
+//! The generic parameters of the impl are resolved by walking the self type and the instantiation
+//! in parallel, e.g. `Wrapper<T>` against `Wrapper<u8>` yields `T = u8`. The bindings are then
+//! substituted into the trait path. Whether the instantiation satisfies the impl's bounds is left
+//! to the compiler.
+
+use std::collections::HashMap;
+
+use proc_macro2::{Group, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{
+    spanned::Spanned, Error, Expr, GenericArgument, GenericParam, Generics, Lifetime,
+    PathArguments, ReturnType, Type,
+};
+
+use crate::tokens::{pretty, tokens_to_string};
+
+/// Maps generic parameter names (lifetimes including their `'`) to their instantiation.
+pub struct Bindings {
+    params: Vec<String>,
+    bindings: HashMap<String, TokenStream>,
+}
+
+impl Bindings {
+    /// Matches `instance` against `pattern`, the self type of the impl declaring `generics`.
+    pub fn unify(generics: &Generics, pattern: &Type, instance: &Type) -> Result<Self, Error> {
+        let params = generics
+            .params
+            .iter()
+            .map(|param| match param {
+                GenericParam::Type(param) => param.ident.to_string(),
+                GenericParam::Lifetime(param) => param.lifetime.to_string(),
+                GenericParam::Const(param) => param.ident.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        let mut bindings = Self {
+            params,
+            bindings: HashMap::new(),
+        };
+
+        if !bindings.unify_type(pattern, instance) {
+            return Err(Error::new(
+                instance.span(),
+                format!(
+                    "`{}` is not an instantiation of `{}`",
+                    pretty(instance),
+                    pretty(pattern)
+                ),
+            ));
+        }
+
+        for param in &generics.params {
+            let (name, span) = match param {
+                GenericParam::Type(param) => (param.ident.to_string(), param.ident.span()),
+                // Lifetimes that only appear in the trait path, the trait is registered for every
+                // lifetime anyway.
+                GenericParam::Lifetime(param) => {
+                    let name = param.lifetime.to_string();
+                    bindings
+                        .bindings
+                        .entry(name)
+                        .or_insert_with(|| quote!('static));
+                    continue;
+                }
+                GenericParam::Const(param) => (param.ident.to_string(), param.ident.span()),
+            };
+            if !bindings.bindings.contains_key(&name) {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "`{name}` can't be inferred from the instantiation `{}`",
+                        pretty(instance)
+                    ),
+                ));
+            }
+        }
+
+        Ok(bindings)
+    }
+
+    /// Replaces every generic parameter in `tokens` with its instantiation.
+    pub fn substitute(&self, tokens: TokenStream) -> TokenStream {
+        let mut out = TokenStream::new();
+        let mut iter = tokens.into_iter();
+
+        while let Some(tt) = iter.next() {
+            match tt {
+                TokenTree::Group(group) => {
+                    let mut substituted =
+                        Group::new(group.delimiter(), self.substitute(group.stream()));
+                    substituted.set_span(group.span());
+                    out.extend([TokenTree::Group(substituted)]);
+                }
+                TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                    // A lifetime is a joint `'` followed by an ident.
+                    match iter.next() {
+                        Some(TokenTree::Ident(ident)) => {
+                            match self.bindings.get(&format!("'{ident}")) {
+                                Some(binding) => out.extend(binding.clone()),
+                                None => {
+                                    out.extend([TokenTree::Punct(punct), TokenTree::Ident(ident)])
+                                }
+                            }
+                        }
+                        Some(tt) => out.extend([TokenTree::Punct(punct), tt]),
+                        None => out.extend([TokenTree::Punct(punct)]),
+                    }
+                }
+                TokenTree::Ident(ident) => match self.bindings.get(&ident.to_string()) {
+                    Some(binding) => out.extend(binding.clone()),
+                    None => out.extend([TokenTree::Ident(ident)]),
+                },
+                tt => out.extend([tt]),
+            }
+        }
+
+        out
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|param| *param == name)
+            .map(String::as_str)
+    }
+
+    fn bind(&mut self, name: &str, instance: &impl ToTokens) -> bool {
+        let instance = instance.to_token_stream();
+        match self.bindings.get(name) {
+            Some(bound) => bound.to_string() == instance.to_string(),
+            None => {
+                self.bindings.insert(name.to_owned(), instance);
+                true
+            }
+        }
+    }
+
+    fn param_ident(&self, ty: &Type) -> Option<String> {
+        match ty {
+            Type::Path(ty) if ty.qself.is_none() => ty
+                .path
+                .get_ident()
+                .map(ToString::to_string)
+                .filter(|ident| self.param(ident).is_some()),
+            _ => None,
+        }
+    }
+
+    fn unify_type(&mut self, pattern: &Type, instance: &Type) -> bool {
+        if let Some(param) = self.param_ident(pattern) {
+            return self.bind(&param, instance);
+        }
+
+        match (pattern, instance) {
+            (Type::Paren(pattern), _) => self.unify_type(&pattern.elem, instance),
+            (_, Type::Paren(instance)) => self.unify_type(pattern, &instance.elem),
+            (Type::Group(pattern), _) => self.unify_type(&pattern.elem, instance),
+            (_, Type::Group(instance)) => self.unify_type(pattern, &instance.elem),
+            (Type::Path(pattern), Type::Path(instance))
+                if pattern.qself.is_none() && instance.qself.is_none() =>
+            {
+                // Only the last segment has to match, `Wrapper<T>` may be instantiated as
+                // `crate::Wrapper<u8>`.
+                match (pattern.path.segments.last(), instance.path.segments.last()) {
+                    (Some(pattern), Some(instance)) if pattern.ident == instance.ident => {
+                        self.unify_path_arguments(&pattern.arguments, &instance.arguments)
+                    }
+                    _ => false,
+                }
+            }
+            (Type::Reference(pattern), Type::Reference(instance)) => {
+                pattern.mutability.is_some() == instance.mutability.is_some()
+                    && self.unify_lifetime(pattern.lifetime.as_ref(), instance.lifetime.as_ref())
+                    && self.unify_type(&pattern.elem, &instance.elem)
+            }
+            (Type::Ptr(pattern), Type::Ptr(instance)) => {
+                pattern.mutability.is_some() == instance.mutability.is_some()
+                    && self.unify_type(&pattern.elem, &instance.elem)
+            }
+            (Type::Slice(pattern), Type::Slice(instance)) => {
+                self.unify_type(&pattern.elem, &instance.elem)
+            }
+            (Type::Array(pattern), Type::Array(instance)) => {
+                self.unify_type(&pattern.elem, &instance.elem)
+                    && self.unify_expr(&pattern.len, &instance.len)
+            }
+            (Type::Tuple(pattern), Type::Tuple(instance)) => {
+                pattern.elems.len() == instance.elems.len()
+                    && pattern
+                        .elems
+                        .iter()
+                        .zip(&instance.elems)
+                        .all(|(pattern, instance)| self.unify_type(pattern, instance))
+            }
+            _ => tokens_to_string(pattern) == tokens_to_string(instance),
+        }
+    }
+
+    fn unify_path_arguments(&mut self, pattern: &PathArguments, instance: &PathArguments) -> bool {
+        match (pattern, instance) {
+            (PathArguments::None, PathArguments::None) => true,
+            (PathArguments::AngleBracketed(pattern), PathArguments::AngleBracketed(instance)) => {
+                pattern.args.len() == instance.args.len()
+                    && pattern
+                        .args
+                        .iter()
+                        .zip(&instance.args)
+                        .all(|(pattern, instance)| self.unify_generic_argument(pattern, instance))
+            }
+            (PathArguments::Parenthesized(pattern), PathArguments::Parenthesized(instance)) => {
+                let output = match (&pattern.output, &instance.output) {
+                    (ReturnType::Default, ReturnType::Default) => true,
+                    (ReturnType::Type(_, pattern), ReturnType::Type(_, instance)) => {
+                        self.unify_type(pattern, instance)
+                    }
+                    _ => false,
+                };
+                output
+                    && pattern.inputs.len() == instance.inputs.len()
+                    && pattern
+                        .inputs
+                        .iter()
+                        .zip(&instance.inputs)
+                        .all(|(pattern, instance)| self.unify_type(pattern, instance))
+            }
+            _ => false,
+        }
+    }
+
+    fn unify_generic_argument(
+        &mut self,
+        pattern: &GenericArgument,
+        instance: &GenericArgument,
+    ) -> bool {
+        match (pattern, instance) {
+            (GenericArgument::Lifetime(pattern), GenericArgument::Lifetime(instance)) => {
+                self.unify_lifetime(Some(pattern), Some(instance))
+            }
+            (GenericArgument::Type(pattern), GenericArgument::Type(instance)) => {
+                self.unify_type(pattern, instance)
+            }
+            // A const parameter is parsed as a type, its instantiation usually as an expression.
+            (GenericArgument::Type(pattern), GenericArgument::Const(instance)) => {
+                match self.param_ident(pattern) {
+                    Some(param) => self.bind(&param, instance),
+                    None => false,
+                }
+            }
+            (GenericArgument::Const(pattern), GenericArgument::Const(instance)) => {
+                self.unify_expr(pattern, instance)
+            }
+            (GenericArgument::AssocType(pattern), GenericArgument::AssocType(instance)) => {
+                pattern.ident == instance.ident && self.unify_type(&pattern.ty, &instance.ty)
+            }
+            _ => tokens_to_string(pattern) == tokens_to_string(instance),
+        }
+    }
+
+    fn unify_expr(&mut self, pattern: &Expr, instance: &Expr) -> bool {
+        match pattern {
+            Expr::Path(path) if path.qself.is_none() => match path.path.get_ident() {
+                Some(ident) if self.param(&ident.to_string()).is_some() => {
+                    self.bind(&ident.to_string(), instance)
+                }
+                _ => tokens_to_string(pattern) == tokens_to_string(instance),
+            },
+            _ => tokens_to_string(pattern) == tokens_to_string(instance),
+        }
+    }
+
+    fn unify_lifetime(&mut self, pattern: Option<&Lifetime>, instance: Option<&Lifetime>) -> bool {
+        match (pattern, instance) {
+            (Some(pattern), Some(instance)) => {
+                let name = pattern.to_string();
+                if self.param(&name).is_some() {
+                    self.bind(&name, instance)
+                } else {
+                    pattern.ident == instance.ident
+                }
+            }
+            // Elided lifetimes can't be registered anyway, the compiler will complain.
+            _ => true,
+        }
+    }
+}
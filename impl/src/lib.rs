@@ -4,9 +4,11 @@ use syn::parse_macro_input;
 
 mod args;
 mod dyncast;
+mod instantiate;
 // mod hash;
 mod linker;
 mod parse;
+mod tokens;
 
 #[proc_macro_attribute]
 pub fn dyncast(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -21,3 +23,23 @@ pub fn dyncast(args: TokenStream, input: TokenStream) -> TokenStream {
         #expanded
     })
 }
+
+#[proc_macro]
+pub fn register(input: TokenStream) -> TokenStream {
+    let registrations = parse_macro_input!(input as parse::Registrations);
+    let expanded = match dyncast::expand_register(&registrations) {
+        Ok(expanded) => expanded,
+        Err(err) => err.to_compile_error(),
+    };
+    TokenStream::from(expanded)
+}
+
+#[proc_macro]
+pub fn register_trait(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as parse::RegisterTrait);
+    let expanded = match dyncast::expand_register_trait(&input) {
+        Ok(expanded) => expanded,
+        Err(err) => err.to_compile_error(),
+    };
+    TokenStream::from(expanded)
+}
@@ -3,7 +3,7 @@
 // From the `linkme` crate:
 // https://github.com/dtolnay/linkme/blob/b841bae328e844b4ff7f9a8d571df771fbecfc18/impl/src/linker.rs
 
-// Note: Keep this in sync with `src/global.rs`.
+// Note: Keep this in sync with `src/global.rs`. `inspect` includes this file as well.
 
 pub mod elf {
     pub const SECTION: &str = "dyncst_entries";
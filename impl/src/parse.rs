@@ -4,17 +4,31 @@
 use proc_macro2::{Span, TokenStream};
 use quote::ToTokens;
 use syn::parse::{Error, Parse, ParseStream};
-use syn::{Attribute, ItemImpl, ItemTrait, Token};
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, DeriveInput, Ident, ItemImpl, ItemTrait, Path, Token, Type, TypeTraitObject,
+    Visibility,
+};
 
 pub enum Item {
     Trait(ItemTrait),
     Impl(ItemImpl),
+    /// A struct, enum or union.
+    Type(DeriveInput),
 }
 
 impl Parse for Item {
     fn parse(input: ParseStream) -> Result<Self, Error> {
         let attrs = Attribute::parse_outer(input)?;
 
+        let ahead = input.fork();
+        ahead.parse::<Visibility>()?;
+        if ahead.peek(Token![struct]) || ahead.peek(Token![enum]) || ahead.peek(Token![union]) {
+            let mut item: DeriveInput = input.parse()?;
+            item.attrs = attrs;
+            return Ok(Item::Type(item));
+        }
+
         let mut lookahead = input.lookahead1();
 
         if lookahead.peek(Token![unsafe]) {
@@ -45,6 +59,73 @@ impl ToTokens for Item {
         match self {
             Item::Trait(item) => item.to_tokens(tokens),
             Item::Impl(item) => item.to_tokens(tokens),
+            Item::Type(item) => item.to_tokens(tokens),
+        }
+    }
+}
+
+/// The input of `register!`, e.g. `dyn Trait => A, B<u8>`.
+pub struct Registrations {
+    pub dyn_trait: TypeTraitObject,
+    pub types: Vec<Type>,
+}
+
+impl Parse for Registrations {
+    fn parse(input: ParseStream) -> Result<Self, Error> {
+        let dyn_trait = match input.parse()? {
+            Type::TraitObject(dyn_trait) if dyn_trait.dyn_token.is_some() => dyn_trait,
+            ty => return Err(Error::new_spanned(ty, "expected `dyn Trait`")),
+        };
+        input.parse::<Token![=>]>()?;
+        let types = Punctuated::<Type, Token![,]>::parse_terminated(input)?;
+        if types.is_empty() {
+            return Err(input.error("expected at least one type"));
         }
+
+        Ok(Registrations {
+            dyn_trait,
+            types: types.into_iter().collect(),
+        })
+    }
+}
+
+/// The input of `register_trait!`, e.g. `dyn Error: Debug, Display` or `pub trait AnyWrite: Write`.
+pub enum RegisterTrait {
+    Dyn {
+        trait_path: Path,
+        supertraits: Vec<Path>,
+    },
+    Local {
+        vis: Visibility,
+        ident: Ident,
+        supertrait: Path,
+    },
+}
+
+impl Parse for RegisterTrait {
+    fn parse(input: ParseStream) -> Result<Self, Error> {
+        if input.parse::<Option<Token![dyn]>>()?.is_some() {
+            let trait_path = input.parse()?;
+            let mut supertraits = Vec::new();
+            if input.parse::<Option<Token![:]>>()?.is_some() {
+                let paths = Punctuated::<Path, Token![,]>::parse_separated_nonempty(input)?;
+                supertraits.extend(paths);
+            }
+            return Ok(RegisterTrait::Dyn {
+                trait_path,
+                supertraits,
+            });
+        }
+
+        let vis = input.parse()?;
+        input.parse::<Token![trait]>()?;
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let supertrait = input.parse()?;
+        Ok(RegisterTrait::Local {
+            vis,
+            ident,
+            supertrait,
+        })
     }
 }
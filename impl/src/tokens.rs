@@ -0,0 +1,21 @@
+#![allow(dead_code)]
+
+// Note: `audit` includes this file as well.
+
+use quote::ToTokens;
+
+pub fn tokens_to_string(tokens: &impl ToTokens) -> String {
+    tokens.to_token_stream().to_string()
+}
+
+/// Like [`tokens_to_string`], without the spaces `TokenStream` puts around punctuation.
+pub fn pretty(tokens: &impl ToTokens) -> String {
+    tokens_to_string(tokens)
+        .replace(" :: ", "::")
+        .replace(" < ", "<")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+}
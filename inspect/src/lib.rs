@@ -0,0 +1,251 @@
+//! Reads the dyncast registrations of a compiled ELF executable or shared object without running
+//! it.
+//!
+//! Every `#[dyncast]` impl places a function pointer into the `dyncst_entries` section. This crate
+//! reads that section, resolves each pointer (taking dynamic relocations into account) and maps it
+//! to the symbol of the function it points to. That function is implemented for the registered
+//! type, its symbol names the type and the trait with both the legacy and the v0 symbol mangling,
+//! e.g. `<app::Json as dyncast::private::RegistrationEntry<dyn app::Format, ..>>::descriptor`.
+//!
+//! Symbols are demangled without the hashes and crate disambiguators the compiler derives from
+//! the crate's metadata, so that entries can be compared across builds with [`diff`].
+
+use std::{collections::BTreeMap, error::Error, fmt};
+
+use object::{
+    Endianness, File, Object, ObjectSection, ObjectSymbol, ObjectSymbolTable, RelocationTarget,
+};
+
+#[path = "../../impl/src/linker.rs"]
+mod linker;
+
+/// A single entry of the `dyncst_entries` section.
+#[derive(Clone, Debug)]
+pub struct Entry {
+    address: u64,
+    target: u64,
+    symbol: Option<String>,
+}
+
+impl Entry {
+    /// The address of the entry itself.
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// The address of the function the entry points to.
+    pub fn target(&self) -> u64 {
+        self.target
+    }
+
+    /// The demangled symbol of the function the entry points to, if the binary still has symbols.
+    pub fn symbol(&self) -> Option<&str> {
+        self.symbol.as_deref()
+    }
+
+    /// The symbol of the entry or its target address, used to compare entries across binaries.
+    pub fn name(&self) -> String {
+        match &self.symbol {
+            Some(symbol) => symbol.clone(),
+            None => format!("{:#x}", self.target),
+        }
+    }
+}
+
+/// The contents of the `dyncst_entries` section of a binary.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    section_address: Option<u64>,
+    entries: Vec<Entry>,
+}
+
+impl Report {
+    /// The address of the section, `None` if the binary doesn't contain any registrations.
+    pub fn section_address(&self) -> Option<u64> {
+        self.section_address
+    }
+
+    /// The entries in section order.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+}
+
+/// The entries that differ between two binaries, see [`diff`].
+#[derive(Clone, Debug, Default)]
+pub struct Diff {
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+impl Diff {
+    /// Entries only present in the new binary.
+    pub fn added(&self) -> &[String] {
+        &self.added
+    }
+
+    /// Entries only present in the old binary.
+    pub fn removed(&self) -> &[String] {
+        &self.removed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// The error returned by [`inspect`].
+#[derive(Debug)]
+pub enum InspectError {
+    /// The data couldn't be parsed as an object file.
+    Parse(object::Error),
+    /// The object file isn't an ELF file.
+    UnsupportedFormat(object::BinaryFormat),
+    /// The section size isn't a multiple of the pointer size.
+    MalformedSection(u64),
+}
+
+impl fmt::Display for InspectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => write!(f, "failed to parse object file: {err}"),
+            Self::UnsupportedFormat(format) => {
+                write!(f, "unsupported object file format `{format:?}`, only ELF is supported")
+            }
+            Self::MalformedSection(size) => write!(
+                f,
+                "section `{}` has a size of {size} bytes which is not a multiple of the pointer size",
+                linker::elf::SECTION
+            ),
+        }
+    }
+}
+
+impl Error for InspectError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<object::Error> for InspectError {
+    fn from(err: object::Error) -> Self {
+        Self::Parse(err)
+    }
+}
+
+/// Reads the `dyncst_entries` section of the ELF file in `data`.
+pub fn inspect(data: &[u8]) -> Result<Report, InspectError> {
+    let file = File::parse(data)?;
+    if file.format() != object::BinaryFormat::Elf {
+        return Err(InspectError::UnsupportedFormat(file.format()));
+    }
+
+    let Some(section) = file.section_by_name(linker::elf::SECTION) else {
+        return Ok(Report::default());
+    };
+
+    let pointer_size = if file.is_64() { 8 } else { 4 };
+    let section_data = section.data()?;
+    if section_data.len() % pointer_size != 0 {
+        return Err(InspectError::MalformedSection(section.size()));
+    }
+
+    // Position independent binaries store the pointers as dynamic relocations, the section itself
+    // only contains the implicit addends (or zeros).
+    let mut relocations = BTreeMap::new();
+    if let Some(dynamic_relocations) = file.dynamic_relocations() {
+        relocations.extend(dynamic_relocations);
+    }
+
+    let symbols = symbols(&file);
+
+    let entries = section_data
+        .chunks_exact(pointer_size)
+        .enumerate()
+        .map(|(idx, chunk)| {
+            let address = section.address() + (idx * pointer_size) as u64;
+            let stored = read_pointer(chunk, file.endianness());
+
+            let target = match relocations.get(&address) {
+                Some(relocation) => {
+                    let base = match relocation.target() {
+                        RelocationTarget::Symbol(index) => file
+                            .dynamic_symbol_table()
+                            .and_then(|table| table.symbol_by_index(index).ok())
+                            .map_or(0, |symbol| symbol.address()),
+                        _ => 0,
+                    };
+                    let addend = if relocation.has_implicit_addend() {
+                        stored.wrapping_add(relocation.addend() as u64)
+                    } else {
+                        relocation.addend() as u64
+                    };
+                    base.wrapping_add(addend)
+                }
+                None => stored,
+            };
+
+            Entry {
+                address,
+                target,
+                symbol: symbols.get(&target).cloned(),
+            }
+        })
+        .collect();
+
+    Ok(Report {
+        section_address: Some(section.address()),
+        entries,
+    })
+}
+
+/// Compares the entries of two binaries by their symbol.
+pub fn diff(old: &Report, new: &Report) -> Diff {
+    let mut counts = BTreeMap::<String, isize>::new();
+    for entry in &old.entries {
+        *counts.entry(entry.name()).or_default() -= 1;
+    }
+    for entry in &new.entries {
+        *counts.entry(entry.name()).or_default() += 1;
+    }
+
+    let mut diff = Diff::default();
+    for (name, count) in counts {
+        let list = if count < 0 {
+            &mut diff.removed
+        } else {
+            &mut diff.added
+        };
+        list.extend(std::iter::repeat(name).take(count.unsigned_abs()));
+    }
+    diff
+}
+
+fn symbols(file: &File<'_>) -> BTreeMap<u64, String> {
+    let mut symbols = BTreeMap::new();
+    for symbol in file.dynamic_symbols().chain(file.symbols()) {
+        if symbol.address() == 0 || !symbol.is_definition() {
+            continue;
+        }
+        if let Ok(name) = symbol.name() {
+            // The alternate form omits the hash of legacy symbols and the crate disambiguators of
+            // v0 symbols, both change with the crate's metadata, e.g. its version.
+            let demangled = format!("{:#}", rustc_demangle::demangle(name));
+            symbols.insert(symbol.address(), demangled);
+        }
+    }
+    symbols
+}
+
+fn read_pointer(chunk: &[u8], endianness: Endianness) -> u64 {
+    match (chunk.len(), endianness) {
+        (8, Endianness::Little) => u64::from_le_bytes(chunk.try_into().unwrap()),
+        (8, Endianness::Big) => u64::from_be_bytes(chunk.try_into().unwrap()),
+        (4, Endianness::Little) => u32::from_le_bytes(chunk.try_into().unwrap()).into(),
+        (4, Endianness::Big) => u32::from_be_bytes(chunk.try_into().unwrap()).into(),
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,82 @@
+use std::{env, fs, process::ExitCode};
+
+use dyncast_inspect::{diff, inspect, Report};
+
+const USAGE: &str = "\
+usage: dyncast-inspect <binary>
+       dyncast-inspect diff <old-binary> <new-binary>";
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let result = match args.as_slice() {
+        ["diff", old, new] => run_diff(old, new),
+        [path] if !path.starts_with('-') => run_inspect(path),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn load(path: &str) -> Result<Report, String> {
+    let data = fs::read(path).map_err(|err| format!("failed to read `{path}`: {err}"))?;
+    inspect(&data).map_err(|err| format!("`{path}`: {err}"))
+}
+
+fn run_inspect(path: &str) -> Result<ExitCode, String> {
+    let report = load(path)?;
+
+    match report.section_address() {
+        Some(address) => println!(
+            "{} entries in `dyncst_entries` at {address:#x}",
+            report.entries().len()
+        ),
+        None => println!("0 entries, no `dyncst_entries` section"),
+    }
+
+    for entry in report.entries() {
+        println!(
+            "{:#010x} -> {:#010x} {}",
+            entry.address(),
+            entry.target(),
+            entry.symbol().unwrap_or("<unknown>")
+        );
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_diff(old: &str, new: &str) -> Result<ExitCode, String> {
+    let old_report = load(old)?;
+    let new_report = load(new)?;
+    let diff = diff(&old_report, &new_report);
+
+    println!(
+        "{} entries -> {} entries",
+        old_report.entries().len(),
+        new_report.entries().len()
+    );
+    for name in diff.removed() {
+        println!("- {name}");
+    }
+    for name in diff.added() {
+        println!("+ {name}");
+    }
+
+    // Like `diff(1)`, exit with 1 if the binaries differ.
+    Ok(if diff.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
@@ -0,0 +1,25 @@
+#![allow(dead_code)]
+
+use dyncast::dyncast;
+
+#[dyncast]
+trait Format {}
+
+struct Json;
+
+#[dyncast]
+impl Format for Json {}
+
+struct Csv;
+
+#[dyncast]
+impl Format for Csv {}
+
+#[cfg(feature = "yaml")]
+struct Yaml;
+
+#[cfg(feature = "yaml")]
+#[dyncast]
+impl Format for Yaml {}
+
+fn main() {}
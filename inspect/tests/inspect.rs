@@ -0,0 +1,107 @@
+use std::{fs, path::Path, process::Command};
+
+use dyncast::dyncast;
+use dyncast_inspect::{diff, inspect, Report};
+
+#[dyncast]
+trait Format {}
+
+struct Json;
+
+#[dyncast]
+impl Format for Json {}
+
+struct Csv;
+
+#[dyncast]
+impl Format for Csv {}
+
+fn current_exe() -> Report {
+    let data = fs::read(std::env::current_exe().unwrap()).unwrap();
+    inspect(&data).unwrap()
+}
+
+/// Builds `tests/fixtures/formats.rs` as a binary crate of its own and inspects it.
+fn build_fixture(version: &str, features: &str) -> Report {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("inspect-fixture");
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::copy(
+        manifest_dir.join("tests/fixtures/formats.rs"),
+        dir.join("src/main.rs"),
+    )
+    .unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"fixture\"\nversion = \"{version}\"\nedition = \"2021\"\n\n\
+             [dependencies]\ndyncast = {{ path = {:?} }}\n\n\
+             [features]\nyaml = []\n\n[workspace]\n",
+            manifest_dir.parent().unwrap()
+        ),
+    )
+    .unwrap();
+    // The lock file of the workspace pins the dependencies to the versions already fetched for it.
+    fs::copy(manifest_dir.join("../Cargo.lock"), dir.join("Cargo.lock")).unwrap();
+
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--offline", "--quiet", "--features", features])
+        .arg("--target-dir")
+        .arg(dir.join("target"))
+        .current_dir(&dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    inspect(&fs::read(dir.join("target/debug/fixture")).unwrap()).unwrap()
+}
+
+#[test]
+fn entries() {
+    let report = current_exe();
+    assert!(report.section_address().is_some());
+    assert_eq!(report.entries().len(), 2);
+
+    let symbols = report
+        .entries()
+        .iter()
+        .map(|entry| entry.symbol().unwrap())
+        .collect::<Vec<_>>();
+    assert!(symbols
+        .iter()
+        .all(|symbol| symbol.contains("dyn inspect::Format")));
+    assert!(symbols
+        .iter()
+        .any(|symbol| symbol.contains("inspect::Json")));
+    assert!(symbols.iter().any(|symbol| symbol.contains("inspect::Csv")));
+}
+
+#[test]
+fn diff_same() {
+    let report = current_exe();
+    assert!(diff(&report, &report).is_empty());
+
+    let diff = diff(&Report::default(), &report);
+    assert_eq!(diff.added().len(), 2);
+    assert!(diff.removed().is_empty());
+}
+
+#[test]
+fn diff_builds() {
+    // The version changes the crate's metadata and with it the hashes of its symbols.
+    let old = build_fixture("0.1.0", "");
+    let new = build_fixture("0.1.1", "");
+    assert_eq!(new.entries().len(), 2);
+    assert!(diff(&old, &new).is_empty());
+
+    let diff = diff(&new, &build_fixture("0.1.1", "yaml"));
+    assert!(diff.removed().is_empty());
+    assert_eq!(diff.added().len(), 1);
+    assert!(diff.added()[0].contains("fixture::Yaml"));
+    assert!(diff.added()[0].contains("dyn fixture::Format"));
+}
+
+#[test]
+fn not_an_object_file() {
+    assert!(inspect(b"definitely not elf").is_err());
+}
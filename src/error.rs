@@ -0,0 +1,83 @@
+use std::{any::TypeId, error::Error, fmt};
+
+use crate::{global::Global, Dyncast};
+
+/// The error returned by the `try_` variants of the casting methods, like
+/// [`try_dyncast_to`](`crate::DyncastExt::try_dyncast_to`).
+///
+/// ```
+/// use dyncast::{dyncast, DyncastExt};
+///
+/// #[dyncast]
+/// trait Bar {}
+///
+/// fn foo(val: &dyn std::any::Any) {
+///     if let Err(err) = val.try_dyncast_to::<dyn Bar>() {
+///         eprintln!("{err}");
+///     }
+/// }
+///
+/// # fn main() {}
+/// ```
+#[derive(Clone, Debug)]
+pub struct DyncastError {
+    source_type_id: TypeId,
+    source_type_name: Option<&'static str>,
+    target_type_name: &'static str,
+    has_registrations: bool,
+}
+
+impl DyncastError {
+    pub(crate) fn new<T: ?Sized + Dyncast>(
+        source_type_id: TypeId,
+        source_type_name: Option<&'static str>,
+    ) -> Self {
+        let global = Global::singleton();
+        Self {
+            source_type_id,
+            source_type_name: source_type_name
+                .or_else(|| global.type_names.get(&source_type_id).copied()),
+            target_type_name: std::any::type_name::<T>(),
+            has_registrations: global.dyn_trait_map.contains_key(&T::dyncast_trait_id()),
+        }
+    }
+
+    /// The [`TypeId`] of the concrete type behind the source of the cast.
+    pub fn source_type_id(&self) -> TypeId {
+        self.source_type_id
+    }
+
+    /// The name of the concrete type behind the source of the cast.
+    ///
+    /// For `dyn Any` sources this is only known if the type has been registered for at least one
+    /// dyncast trait.
+    pub fn source_type_name(&self) -> Option<&'static str> {
+        self.source_type_name
+    }
+
+    /// The name of the `dyn Trait` that was casted to.
+    pub fn target_type_name(&self) -> &'static str {
+        self.target_type_name
+    }
+
+    /// Whether any type has been registered for the target `dyn Trait`.
+    pub fn has_registrations(&self) -> bool {
+        self.has_registrations
+    }
+}
+
+impl fmt::Display for DyncastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.source_type_name {
+            Some(source_type_name) => write!(f, "`{source_type_name}`")?,
+            None => write!(f, "{:?}", self.source_type_id)?,
+        }
+        write!(f, " is not registered for `{}`", self.target_type_name)?;
+        if !self.has_registrations {
+            write!(f, " (no type is registered for it)")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for DyncastError {}
@@ -0,0 +1,67 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+};
+
+/// Makes a trait that can't be annotated with [`dyncast`](macro@crate::dyncast) a cast target.
+///
+/// `register_trait!(dyn Trait)` generates what the attribute generates for a trait definition.
+/// Types are registered for the trait like for any other dyncast trait, with [`dyncast`] on their
+/// impls. The dyncast supertraits of the trait can be listed after a colon, types are then
+/// registered for them as well.
+///
+/// The generated impls are subject to the orphan rules, this form can therefore only be used in
+/// the crate defining the trait. This crate registers [`Display`], [`Debug`] and [`Error`]:
+///
+/// ```
+/// use std::fmt::{self, Display};
+///
+/// use dyncast::{dyncast, DyncastExt};
+///
+/// struct Version(u8);
+///
+/// #[dyncast]
+/// impl Display for Version {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "v{}", self.0)
+///     }
+/// }
+///
+/// fn main() {
+///     let version = &Version(2) as &dyn std::any::Any;
+///     assert_eq!(version.dyncast_to::<dyn Display>().unwrap().to_string(), "v2");
+/// }
+/// ```
+///
+/// A `&dyn Trait` of a registered trait can't be cast from, the concrete type isn't known for
+/// it. The [`TraitInfo`](crate::TraitInfo) of the trait refers to the invocation of this macro.
+///
+/// Traits of other crates are made cast targets through a local dyncast trait instead.
+/// `register_trait!(trait Local: Foreign)` declares `Local` with `Foreign` as its only supertrait
+/// and implements it for every type implementing `Foreign`. Types are registered for it with
+/// [`register!`](crate::register) or at their definition, casting to `dyn Local` gives access to
+/// the methods of `Foreign`:
+///
+/// ```
+/// use std::io::Write;
+///
+/// use dyncast::DyncastExt;
+///
+/// dyncast::register_trait!(pub trait AnyWrite: Write);
+///
+/// dyncast::register!(dyn AnyWrite => Vec<u8>);
+///
+/// fn main() {
+///     let mut buf = Vec::<u8>::new();
+///     let writer = (&mut buf as &mut dyn std::any::Any).dyncast_to_mut::<dyn AnyWrite>();
+///     writer.unwrap().write_all(b"boba").unwrap();
+///     assert_eq!(buf, b"boba");
+/// }
+/// ```
+///
+/// [`dyncast`]: macro@crate::dyncast
+pub use dyncast_impl::register_trait;
+
+register_trait!(dyn Display);
+register_trait!(dyn Debug);
+register_trait!(dyn Error: Debug, Display);
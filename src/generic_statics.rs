@@ -36,11 +36,11 @@ pub unsafe fn generic_static<T: 'static>() -> &'static T {
     unsafe {
         std::arch::asm!(
             "/* {type_id} */",
-            "adrp {x}, 1f@PAGE",
-            "add {x}, {x}, 1f@PAGEOFF",
+            "adrp {x}, 2f@PAGE",
+            "add {x}, {x}, 2f@PAGEOFF",
             ".pushsection __DATA,__data",
             ".p2align 4, 0",
-            "1: .zero 64",
+            "2: .zero 64",
             ".popsection",
             type_id = in(reg) type_id,
             x = out(reg) addr,
@@ -55,11 +55,11 @@ pub unsafe fn generic_static<T: 'static>() -> &'static T {
     unsafe {
         std::arch::asm!(
             "/* {type_id} */",
-            "adrp {x}, 1f",
-            "add {x}, {x}, :lo12:1f",
+            "adrp {x}, 2f",
+            "add {x}, {x}, :lo12:2f",
             ".pushsection .bss.generic_statics,\"aw\",@nobits",
             ".p2align 4, 0",
-            "1: .zero 64",
+            "2: .zero 64",
             ".popsection",
             type_id = in(reg) type_id,
             x = out(reg) addr,
@@ -74,10 +74,10 @@ pub unsafe fn generic_static<T: 'static>() -> &'static T {
     unsafe {
         std::arch::asm!(
             "/* {type_id} */",
-            "lea {x}, [rip + 1f]",
+            "lea {x}, [rip + 2f]",
             ".pushsection __DATA,__data",
             ".p2align 4, 0",
-            "1: .zero 64",
+            "2: .zero 64",
             ".popsection",
             type_id = in(reg) type_id,
             x = out(reg) addr,
@@ -92,10 +92,10 @@ pub unsafe fn generic_static<T: 'static>() -> &'static T {
     unsafe {
         std::arch::asm!(
             "/* {type_id} */",
-            "lea {x}, [rip + 1f]",
+            "lea {x}, [rip + 2f]",
             ".pushsection .bss.generic_statics,\"aw\",@nobits",
             ".p2align 4, 0",
-            "1: .zero 64",
+            "2: .zero 64",
             ".popsection",
             type_id = in(reg) type_id,
             x = out(reg) addr,
@@ -107,10 +107,10 @@ pub unsafe fn generic_static<T: 'static>() -> &'static T {
     unsafe {
         std::arch::asm!(
             "/* {type_id} */",
-            "lea {x}, [rip + 1f]",
+            "lea {x}, [rip + 2f]",
             ".pushsection .bss.generic_statics,\"bw\"",
             ".p2align 4, 0",
-            "1: .zero 64",
+            "2: .zero 64",
             ".popsection",
             type_id = in(reg) type_id,
             x = out(reg) addr,
@@ -1,6 +1,15 @@
-use std::{any::TypeId, collections::HashMap, hash::Hash, mem, sync::OnceLock};
-
-use crate::private::{Descriptor, Entry, PartialDescriptor};
+use std::{
+    any::TypeId,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    mem,
+    sync::OnceLock,
+};
+
+use crate::{
+    private::{Descriptor, Entry, PartialDescriptor},
+    registry::{TraitInfo, TypeInfo},
+};
 
 #[cfg(any(
     target_os = "none",
@@ -57,6 +66,10 @@ pub type SelfTypeId = TypeId;
 
 pub struct Global {
     pub dyn_trait_map: HashMap<DynTraitTypeId, HashMap<SelfTypeId, PartialDescriptor>>,
+    pub type_names: HashMap<SelfTypeId, &'static str>,
+    pub implementors: HashMap<DynTraitTypeId, Vec<TypeInfo>>,
+    pub traits: HashMap<SelfTypeId, Vec<DynTraitTypeId>>,
+    pub trait_infos: HashMap<DynTraitTypeId, TraitInfo>,
 }
 
 unsafe impl Send for Global {}
@@ -77,6 +90,46 @@ impl Global {
             .collect::<Vec<_>>()
         };
 
+        // Registrations for a trait imply registrations for its dyncast supertraits, unless the
+        // type has been registered for them explicitly.
+        let mut registered = descriptors
+            .iter()
+            .map(|descriptor| (descriptor.dyn_trait_id, descriptor.self_type_id))
+            .collect::<HashSet<_>>();
+        let mut pending = descriptors.clone();
+        while let Some(descriptor) = pending.pop() {
+            for supertrait in (descriptor.supertraits)() {
+                if registered.insert((supertrait.dyn_trait_id, supertrait.self_type_id)) {
+                    let supertrait = Descriptor {
+                        auto_traits: descriptor.auto_traits,
+                        module_path: descriptor.module_path,
+                        file: descriptor.file,
+                        line: descriptor.line,
+                        ..supertrait
+                    };
+                    descriptors.push(supertrait);
+                    pending.push(supertrait);
+                }
+            }
+        }
+
+        let mut trait_infos = HashMap::new();
+        for descriptor in &descriptors {
+            trait_infos
+                .entry(descriptor.dyn_trait_id)
+                .or_insert_with(descriptor.dyn_trait_info);
+        }
+
+        // A type can be registered twice, e.g. by `#[dyncast]` on the impl and by `register!`.
+        let mut seen = HashSet::new();
+        descriptors
+            .retain(|descriptor| seen.insert((descriptor.dyn_trait_id, descriptor.self_type_id)));
+        let registered_traits = descriptors
+            .iter()
+            .map(|descriptor| descriptor.dyn_trait_id)
+            .collect::<HashSet<_>>();
+        trait_infos.retain(|dyn_trait_id, _| registered_traits.contains(dyn_trait_id));
+
         descriptors.sort_unstable_by_key(|descriptor| descriptor.dyn_trait_id);
 
         let dyn_trait_map: HashMap<DynTraitTypeId, HashMap<SelfTypeId, PartialDescriptor>> =
@@ -87,13 +140,55 @@ impl Global {
                     (
                         descriptor.self_type_id,
                         PartialDescriptor {
-                            attach_vtable_fn: descriptor.attach_vtable_fn,
+                            vtable: descriptor.vtable,
+                            auto_traits: descriptor.auto_traits,
                         },
                     )
                 },
             );
 
-        Self { dyn_trait_map }
+        let mut implementors: HashMap<DynTraitTypeId, Vec<TypeInfo>> = group_and_collect(
+            descriptors.iter().copied(),
+            |descriptor| descriptor.dyn_trait_id,
+            |descriptor| TypeInfo {
+                type_id: descriptor.self_type_id,
+                type_name: descriptor.self_type_name,
+                module_path: descriptor.module_path,
+                file: descriptor.file,
+                line: descriptor.line,
+            },
+        );
+        for implementors in implementors.values_mut() {
+            implementors
+                .sort_unstable_by_key(|implementor| (implementor.type_name, implementor.type_id));
+        }
+
+        descriptors.sort_unstable_by_key(|descriptor| descriptor.self_type_id);
+
+        let mut traits: HashMap<SelfTypeId, Vec<DynTraitTypeId>> = group_and_collect(
+            descriptors.iter().copied(),
+            |descriptor| descriptor.self_type_id,
+            |descriptor| descriptor.dyn_trait_id,
+        );
+        for traits in traits.values_mut() {
+            traits.sort_unstable_by_key(|dyn_trait_id| {
+                let info: &TraitInfo = &trait_infos[dyn_trait_id];
+                (info.type_name, info.type_id)
+            });
+        }
+
+        let type_names = descriptors
+            .iter()
+            .map(|descriptor| (descriptor.self_type_id, descriptor.self_type_name))
+            .collect();
+
+        Self {
+            dyn_trait_map,
+            type_names,
+            implementors,
+            traits,
+            trait_infos,
+        }
     }
 }
 
@@ -9,7 +9,7 @@
 //!
 //! This library provides opt-in type downcasting to `dyn Trait`s.
 //!
-//! The entrypoint for this library is the [`dyncast`] proc-macro, which should be applied on every `trait` and `impl`, you'd want to enable downcasting to. The proc-macro will additionally implement [`Dyncast`] for the selected trait (`dyn Trait`), which can be used to check whether a concrete type implements such trait.
+//! The entrypoint for this library is the [`dyncast`](macro@dyncast) proc-macro, which should be applied on every `trait` and `impl`, you'd want to enable downcasting to. The proc-macro will additionally implement [`Dyncast`] for the selected trait (`dyn Trait`), which can be used to check whether a concrete type implements such trait.
 //!
 //! ### Example
 //!
@@ -30,15 +30,25 @@
 //!     }
 //! }
 //!
-//! #[test]
-//! fn boba() {
+//! fn main() {
 //!     let a = &() as &dyn Any;
 //!     assert!(a.dyncast_to::<dyn Foo>().is_some());
 //! }
 //! ```
-use std::any::Any;
+// The code generated by the proc-macros refers to this crate as `::dyncast`, including the
+// `register_trait!` invocations in `foreign`.
+extern crate self as dyncast;
 
-/// [This](`dyncast`) proc-macro can be used on trait definitions and trait impls.
+use std::{
+    any::{Any, TypeId},
+    pin::Pin,
+    rc::{self, Rc},
+    sync::{self, Arc},
+};
+
+use crate::private::{PartialDescriptor, SourceHook};
+
+/// [This](macro@dyncast) proc-macro can be used on trait definitions and trait impls.
 ///
 /// ```
 /// use dyncast::dyncast;
@@ -52,7 +62,7 @@ use std::any::Any;
 /// # fn main() {}
 /// ```
 ///
-/// [`dyncast`] also supports traits with generics. However, this is limited type parameters.
+/// [`dyncast`](macro@dyncast) also supports traits with generics, including const parameters and defaults.
 ///
 /// ```
 /// use dyncast::dyncast;
@@ -63,24 +73,449 @@ use std::any::Any;
 /// #[dyncast]
 /// impl Foo<String> for () {}
 ///
+/// #[dyncast]
+/// trait Codec<const N: usize, T = Vec<u8>> {}
+///
+/// #[dyncast]
+/// impl Codec<4> for () {}
+///
+/// # fn main() {}
+/// ```
+///
+/// Traits with lifetime parameters are registered as, and can be cast to, the higher-ranked
+/// `dyn for<'a> Trait<'a>`. Their impls therefore have to hold for every lifetime:
+///
+/// ```
+/// use dyncast::dyncast;
+///
+/// #[dyncast]
+/// trait Visitor<'a> {
+///     fn visit(&mut self, name: &'a str);
+/// }
+///
+/// struct Counter(usize);
+///
+/// #[dyncast]
+/// impl<'a> Visitor<'a> for Counter {
+///     fn visit(&mut self, _name: &'a str) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// fn visit_all(visitor: &mut dyn std::any::Any, names: &[String]) {
+///     if let Some(visitor) = dyncast::DyncastExt::dyncast_to_mut::<dyn for<'a> Visitor<'a>>(visitor) {
+///         for name in names {
+///             visitor.visit(name);
+///         }
+///     }
+/// }
+///
+/// # fn main() {}
+/// ```
+///
+/// Associated types have to be bound when casting, every binding is registered separately. A cast
+/// to `dyn Source<Item = u32>` only succeeds for implementors whose `Item` is `u32`:
+///
+/// ```
+/// use dyncast::{dyncast, DyncastExt};
+///
+/// #[dyncast]
+/// trait Source {
+///     type Item;
+///
+///     fn next(&mut self) -> Option<Self::Item>;
+/// }
+///
+/// struct Counter(u32);
+///
+/// #[dyncast]
+/// impl Source for Counter {
+///     type Item = u32;
+///
+///     fn next(&mut self) -> Option<u32> {
+///         self.0 += 1;
+///         Some(self.0)
+///     }
+/// }
+///
+/// # fn main() {
+/// let counter: &dyn std::any::Any = &Counter(0);
+/// assert!(counter.dyncast_to::<dyn Source<Item = u32>>().is_some());
+/// assert!(counter.dyncast_to::<dyn Source<Item = String>>().is_none());
+/// # }
+/// ```
+///
+/// Types registered for a trait are registered for its dyncast supertraits as well, without
+/// [`dyncast`](macro@dyncast) on the impls of the supertraits. A `&dyn Sub` can therefore be upcast to
+/// `&dyn Super` with [`dyncast_to`](`DyncastExt::dyncast_to`), see also [`supertraits_of`].
+///
+/// Impls of traits registered with `register_trait!(dyn Trait)`, like [`std::fmt::Display`], are
+/// annotated the same way. Traits of other crates are made cast targets through a local trait, see
+/// [`register_trait`].
+///
+/// Forgetting [`dyncast`](macro@dyncast) on an impl isn't an error by default, the type is just never registered.
+/// Traits declared with `#[dyncast(strict)]` reject such impls at compile time instead:
+///
+/// ```compile_fail
+/// use dyncast::dyncast;
+///
+/// #[dyncast(strict)]
+/// trait Foo {}
+///
+/// // error: the impl of `Foo` for `()` is missing #[dyncast]
+/// impl Foo for () {}
+///
+/// # fn main() {}
+/// ```
+///
+/// Generic impls have to list the instantiations that get registered. Each instantiation has to
+/// match the self type of the impl and satisfy its bounds:
+///
+/// ```
+/// use dyncast::dyncast;
+///
+/// #[dyncast]
+/// trait Foo {}
+///
+/// struct Wrapper<T>(T);
+///
+/// #[dyncast(instantiate(Wrapper<u8>, Wrapper<String>))]
+/// impl<T: ToString> Foo for Wrapper<T> {}
+///
 /// # fn main() {}
 /// ```
+///
+/// A generic impl satisfies `#[dyncast(strict)]` for all of its types, including blanket impls like
+/// `impl<T: ToString> Foo for T`, but only the instantiations are registered.
+///
+/// Traits declared with `#[dyncast(sealed)]` only accept registrations from the crate defining
+/// them, registrations in other crates don't compile. Dyncast traits with a sealed supertrait are
+/// sealed as well and have to be defined in the same crate. Both arguments can be combined, as in
+/// `#[dyncast(strict, sealed)]`.
+///
+/// Instead of annotating each impl, a struct, enum or union can list the traits it's registered
+/// for at its definition:
+///
+/// ```
+/// use std::fmt::{self, Display};
+///
+/// use dyncast::{dyncast, DyncastExt};
+///
+/// #[dyncast]
+/// trait Foo {}
+///
+/// #[dyncast(Display, Foo)]
+/// struct Bar;
+///
+/// impl Display for Bar {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("bar")
+///     }
+/// }
+///
+/// impl Foo for Bar {}
+///
+/// fn main() {
+///     let bar = &Bar as &dyn std::any::Any;
+///     assert_eq!(bar.dyncast_to::<dyn Display>().unwrap().to_string(), "bar");
+///     assert!(bar.dyncast_to::<dyn Foo>().is_some());
+/// }
+/// ```
+///
+/// The traits are written as after `dyn`, e.g. `Source<Item = u32>` or `for<'a> Visitor<'a>`.
+/// This counts as annotating the impls, they must not be annotated as well. Generic types can't be
+/// registered this way, [`register!`] their instantiations instead.
 pub use dyncast_impl::dyncast;
 
+/// Registers existing impls of a dyncast trait, for impls that can't be annotated with
+/// [`dyncast`](macro@dyncast), like the ones generated by derive macros or defined in other crates.
+///
+/// ```
+/// use dyncast::{dyncast, DyncastExt};
+///
+/// #[dyncast]
+/// trait Named {
+///     fn name(&self) -> String;
+/// }
+///
+/// macro_rules! impl_named {
+///     ($($ty:ty),*) => {
+///         $(impl Named for $ty {
+///             fn name(&self) -> String {
+///                 stringify!($ty).to_owned()
+///             }
+///         })*
+///     };
+/// }
+///
+/// impl_named!(u8, Vec<u8>);
+///
+/// dyncast::register!(dyn Named => u8, Vec<u8>);
+///
+/// fn main() {
+///     let bytes = &vec![1u8] as &dyn std::any::Any;
+///     assert_eq!(bytes.dyncast_to::<dyn Named>().unwrap().name(), "Vec<u8>");
+/// }
+/// ```
+///
+/// The trait is given without auto traits, they are detected for every type. Registrations through
+/// this macro don't satisfy `#[dyncast(strict)]`, impls of strict traits have to be annotated.
+pub use dyncast_impl::register;
+
 #[doc(hidden)]
 pub mod private;
+pub mod raw;
+pub mod registry;
 
+mod error;
+mod foreign;
 mod generic_statics;
 mod global;
 mod map;
 mod once;
 mod ptr;
 
-pub trait Dyncast: Any {
-    fn dyncast_from<T: ?Sized + Any>(source: &T) -> Option<&Self>;
+pub use crate::{
+    error::DyncastError,
+    foreign::register_trait,
+    registry::{
+        implementors, implements, implements_type, supertraits_of, traits_of, TraitInfo, TypeInfo,
+    },
+};
+
+/// Implemented by the [`dyncast`](macro@dyncast) proc-macro for `dyn Trait` and its auto trait variants.
+///
+/// # Safety
+///
+/// [`dyncast_descriptor`](`Dyncast::dyncast_descriptor`) must only return descriptors whose vtable
+/// belongs to the type with the given [`TypeId`] and to `Self`, or one of its auto trait variants
+/// the type implements. [`dyncast_trait_id`](`Dyncast::dyncast_trait_id`) must return the
+/// [`TypeId`] of `Self` without its auto traits.
+pub unsafe trait Dyncast: Any {
+    #[doc(hidden)]
+    fn dyncast_descriptor(self_type_id: TypeId) -> Option<PartialDescriptor>;
+
+    /// The [`TypeId`] of the `dyn Trait` the registrations are keyed by, which excludes any
+    /// additional auto traits.
+    #[doc(hidden)]
+    #[inline]
+    fn dyncast_trait_id() -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    #[doc(hidden)]
+    fn dyncast_trait_info() -> TraitInfo;
+
+    /// Whether only the crate defining the trait may register types for it, see
+    /// [`TraitInfo::is_sealed`].
+    #[doc(hidden)]
+    const SEALED: bool = false;
+
+    #[inline]
+    fn dyncast_from<T: ?Sized + DyncastSource>(source: &T) -> Option<&Self> {
+        let descriptor = Self::dyncast_descriptor(T::dyncast_type_id(source))?;
+        Some(unsafe { &*descriptor.attach_vtable::<Self>(source as *const T as *const ()) })
+    }
+
+    #[inline]
+    fn dyncast_from_mut<T: ?Sized + DyncastSource>(source: &mut T) -> Option<&mut Self> {
+        let descriptor = Self::dyncast_descriptor(T::dyncast_type_id(source))?;
+        Some(unsafe { &mut *descriptor.attach_vtable_mut::<Self>(source as *mut T as *mut ()) })
+    }
+
+    /// Like [`dyncast_from`](`Dyncast::dyncast_from`), but returns a [`DyncastError`] describing
+    /// the failed cast.
+    #[inline]
+    fn try_dyncast_from<T: ?Sized + DyncastSource>(source: &T) -> Result<&Self, DyncastError> {
+        let type_id = T::dyncast_type_id(source);
+        let type_name = T::dyncast_type_name(source);
+        Self::dyncast_from(source).ok_or_else(|| DyncastError::new::<Self>(type_id, type_name))
+    }
+
+    /// Like [`dyncast_from_mut`](`Dyncast::dyncast_from_mut`), but returns a [`DyncastError`]
+    /// describing the failed cast.
+    #[inline]
+    fn try_dyncast_from_mut<T: ?Sized + DyncastSource>(
+        source: &mut T,
+    ) -> Result<&mut Self, DyncastError> {
+        let type_id = T::dyncast_type_id(source);
+        let type_name = T::dyncast_type_name(source);
+        Self::dyncast_from_mut(source).ok_or_else(|| DyncastError::new::<Self>(type_id, type_name))
+    }
+
+    /// Converts an owned `source` into a `Box<Self>`, handing back `source` if the cast fails.
+    #[inline]
+    fn dyncast_from_box<T: ?Sized + DyncastSource>(source: Box<T>) -> Result<Box<Self>, Box<T>> {
+        let Some(descriptor) = Self::dyncast_descriptor(T::dyncast_type_id(&*source)) else {
+            return Err(source);
+        };
+        let ptr = Box::into_raw(source);
+        Ok(unsafe { Box::from_raw(descriptor.attach_vtable_mut::<Self>(ptr as *mut ())) })
+    }
+
+    /// Casts a pinned reference, the pinning guarantee carries over to the returned reference.
+    #[inline]
+    fn dyncast_from_pin<T: ?Sized + DyncastSource>(source: Pin<&T>) -> Option<Pin<&Self>> {
+        // SAFETY: Only the vtable of the pointer changes, the pointee stays where it is.
+        Self::dyncast_from(source.get_ref()).map(|target| unsafe { Pin::new_unchecked(target) })
+    }
+
+    /// Casts a pinned mutable reference, the pinning guarantee carries over to the returned
+    /// reference.
+    #[inline]
+    fn dyncast_from_pin_mut<T: ?Sized + DyncastSource>(
+        source: Pin<&mut T>,
+    ) -> Option<Pin<&mut Self>> {
+        // SAFETY: The pointee is never moved, it is immediately pinned again with a different
+        // vtable.
+        let source = unsafe { source.get_unchecked_mut() };
+        Self::dyncast_from_mut(source).map(|target| unsafe { Pin::new_unchecked(target) })
+    }
+
+    /// Converts a pinned `source` into a `Pin<Box<Self>>`, handing back `source` if the cast
+    /// fails.
+    #[inline]
+    fn dyncast_from_pin_box<T: ?Sized + DyncastSource>(
+        source: Pin<Box<T>>,
+    ) -> Result<Pin<Box<Self>>, Pin<Box<T>>> {
+        // SAFETY: The pointee is never moved, it is immediately pinned again on both paths.
+        let source = unsafe { Pin::into_inner_unchecked(source) };
+        Self::dyncast_from_box(source)
+            .map(Box::into_pin)
+            .map_err(Box::into_pin)
+    }
+
+    /// Converts `source` into an `Rc<Self>` sharing the same allocation, handing back `source` if
+    /// the cast fails.
+    #[inline]
+    fn dyncast_from_rc<T: ?Sized + DyncastSource>(source: Rc<T>) -> Result<Rc<Self>, Rc<T>> {
+        let Some(descriptor) = Self::dyncast_descriptor(T::dyncast_type_id(&*source)) else {
+            return Err(source);
+        };
+        let ptr = Rc::into_raw(source);
+        Ok(unsafe { Rc::from_raw(descriptor.attach_vtable::<Self>(ptr as *const ())) })
+    }
+
+    /// Converts `source` into an `Arc<Self>` sharing the same allocation, handing back `source` if
+    /// the cast fails.
+    #[inline]
+    fn dyncast_from_arc<T: ?Sized + DyncastSource>(source: Arc<T>) -> Result<Arc<Self>, Arc<T>> {
+        let Some(descriptor) = Self::dyncast_descriptor(T::dyncast_type_id(&*source)) else {
+            return Err(source);
+        };
+        let ptr = Arc::into_raw(source);
+        Ok(unsafe { Arc::from_raw(descriptor.attach_vtable::<Self>(ptr as *const ())) })
+    }
+
+    /// Converts `source` into an `rc::Weak<Self>` sharing the same allocation, handing back
+    /// `source` if the cast fails.
+    ///
+    /// The type of the pointee can only be determined while it is alive, therefore this fails
+    /// for dangling weak pointers.
+    #[inline]
+    fn dyncast_from_rc_weak<T: ?Sized + DyncastSource>(
+        source: rc::Weak<T>,
+    ) -> Result<rc::Weak<Self>, rc::Weak<T>> {
+        let descriptor = source
+            .upgrade()
+            .and_then(|source| Self::dyncast_descriptor(T::dyncast_type_id(&*source)));
+        let Some(descriptor) = descriptor else {
+            return Err(source);
+        };
+        let ptr = rc::Weak::into_raw(source);
+        Ok(unsafe { rc::Weak::from_raw(descriptor.attach_vtable::<Self>(ptr as *const ())) })
+    }
+
+    /// Converts `source` into a `sync::Weak<Self>` sharing the same allocation, handing back
+    /// `source` if the cast fails.
+    ///
+    /// The type of the pointee can only be determined while it is alive, therefore this fails
+    /// for dangling weak pointers.
+    #[inline]
+    fn dyncast_from_arc_weak<T: ?Sized + DyncastSource>(
+        source: sync::Weak<T>,
+    ) -> Result<sync::Weak<Self>, sync::Weak<T>> {
+        let descriptor = source
+            .upgrade()
+            .and_then(|source| Self::dyncast_descriptor(T::dyncast_type_id(&*source)));
+        let Some(descriptor) = descriptor else {
+            return Err(source);
+        };
+        let ptr = sync::Weak::into_raw(source);
+        Ok(unsafe { sync::Weak::from_raw(descriptor.attach_vtable::<Self>(ptr as *const ())) })
+    }
+}
+
+/// Types that can act as the source of a cast.
+///
+/// This is implemented for every `T: ?Sized + Any`. Trait objects of traits annotated with
+/// [`dyncast`](macro@dyncast) find their concrete type through a hidden supertrait, so a `&dyn Trait` can be cast
+/// to another `dyn Trait` without requiring [`Any`] as a supertrait. Any other trait object, like
+/// `dyn Any + Sync` or the one of a trait with [`Any`] as supertrait, finds it through
+/// [`Any::type_id`]. For a trait object with neither, the trait object itself is the source type.
+///
+/// ```
+/// use dyncast::{dyncast, DyncastExt};
+///
+/// #[dyncast]
+/// trait Foo {}
+///
+/// #[dyncast]
+/// trait Bar {}
+///
+/// #[dyncast]
+/// impl Foo for () {}
+///
+/// #[dyncast]
+/// impl Bar for () {}
+///
+/// fn main() {
+///     let foo = &() as &dyn Foo;
+///     assert!(foo.dyncast_to::<dyn Bar>().is_some());
+/// }
+/// ```
+///
+/// # Safety
+///
+/// [`dyncast_type_id`](`DyncastSource::dyncast_type_id`) must return the [`TypeId`] of the
+/// concrete type behind `self`.
+pub unsafe trait DyncastSource {
+    fn dyncast_type_id(&self) -> TypeId;
+
+    /// The name of the concrete type behind `self`, if it's known without the registry.
+    #[inline(always)]
+    fn dyncast_type_name(&self) -> Option<&'static str> {
+        None
+    }
 }
 
-/// Provides a shorthand method [`dyncast_to`](`DyncastExt::dyncast_to`).
+unsafe impl<T: ?Sized + Any> DyncastSource for T {
+    #[inline(always)]
+    fn dyncast_type_id(&self) -> TypeId {
+        // The hook only differs from `T` for trait objects of `#[dyncast]` traits, it's dispatched
+        // through their vtable.
+        let type_id = SourceHook::__dyncast_source_type_id(self);
+        if type_id != TypeId::of::<T>() {
+            type_id
+        } else {
+            Any::type_id(self)
+        }
+    }
+
+    #[inline(always)]
+    fn dyncast_type_name(&self) -> Option<&'static str> {
+        if SourceHook::__dyncast_source_type_id(self) != TypeId::of::<T>() {
+            Some(SourceHook::__dyncast_source_type_name(self))
+        } else if Any::type_id(self) == TypeId::of::<T>() {
+            Some(std::any::type_name::<T>())
+        } else {
+            None
+        }
+    }
+}
+
+/// Provides the shorthand methods [`dyncast_to`](`DyncastExt::dyncast_to`) and
+/// [`dyncast_to_mut`](`DyncastExt::dyncast_to_mut`).
 ///
 /// ```
 /// use dyncast::{dyncast, DyncastExt};
@@ -96,11 +531,166 @@ pub trait Dyncast: Any {
 /// ```
 pub trait DyncastExt {
     fn dyncast_to<T: ?Sized + Dyncast>(&self) -> Option<&T>;
+
+    fn dyncast_to_mut<T: ?Sized + Dyncast>(&mut self) -> Option<&mut T>;
+
+    fn try_dyncast_to<T: ?Sized + Dyncast>(&self) -> Result<&T, DyncastError>;
+
+    fn try_dyncast_to_mut<T: ?Sized + Dyncast>(&mut self) -> Result<&mut T, DyncastError>;
 }
 
-impl<T: ?Sized + Any> DyncastExt for T {
+impl<T: ?Sized + DyncastSource> DyncastExt for T {
     #[inline(always)]
     fn dyncast_to<D: ?Sized + Dyncast>(&self) -> Option<&D> {
         D::dyncast_from(self)
     }
+
+    #[inline(always)]
+    fn dyncast_to_mut<D: ?Sized + Dyncast>(&mut self) -> Option<&mut D> {
+        D::dyncast_from_mut(self)
+    }
+
+    #[inline(always)]
+    fn try_dyncast_to<D: ?Sized + Dyncast>(&self) -> Result<&D, DyncastError> {
+        D::try_dyncast_from(self)
+    }
+
+    #[inline(always)]
+    fn try_dyncast_to_mut<D: ?Sized + Dyncast>(&mut self) -> Result<&mut D, DyncastError> {
+        D::try_dyncast_from_mut(self)
+    }
+}
+
+/// Provides the owned conversion [`dyncast_box`](`DyncastBoxExt::dyncast_box`).
+///
+/// ```
+/// use std::any::Any;
+///
+/// use dyncast::{dyncast, DyncastBoxExt};
+///
+/// #[dyncast]
+/// trait Bar {}
+///
+/// fn foo(val: Box<dyn Any + Send>) -> Box<dyn Any + Send> {
+///     val.dyncast_box::<dyn Bar>().err().unwrap()
+/// }
+///
+/// # fn main() {}
+/// ```
+pub trait DyncastBoxExt {
+    fn dyncast_box<T: ?Sized + Dyncast>(self: Box<Self>) -> Result<Box<T>, Box<Self>>;
+}
+
+impl<T: ?Sized + DyncastSource> DyncastBoxExt for T {
+    #[inline(always)]
+    fn dyncast_box<D: ?Sized + Dyncast>(self: Box<Self>) -> Result<Box<D>, Box<Self>> {
+        D::dyncast_from_box(self)
+    }
+}
+
+/// Provides the pinned conversions [`dyncast_pin`](`DyncastPinExt::dyncast_pin`),
+/// [`dyncast_pin_mut`](`DyncastPinExt::dyncast_pin_mut`) and
+/// [`dyncast_pin_box`](`DyncastPinExt::dyncast_pin_box`).
+///
+/// ```
+/// use std::{any::Any, pin::Pin};
+///
+/// use dyncast::{dyncast, DyncastPinExt};
+///
+/// #[dyncast]
+/// trait Machine {
+///     fn step(self: Pin<&mut Self>);
+/// }
+///
+/// fn foo(val: Pin<&mut dyn Any>) {
+///     if let Some(machine) = val.dyncast_pin_mut::<dyn Machine>() {
+///         machine.step();
+///     }
+/// }
+///
+/// # fn main() {}
+/// ```
+pub trait DyncastPinExt {
+    fn dyncast_pin<T: ?Sized + Dyncast>(self: Pin<&Self>) -> Option<Pin<&T>>;
+
+    fn dyncast_pin_mut<T: ?Sized + Dyncast>(self: Pin<&mut Self>) -> Option<Pin<&mut T>>;
+
+    fn dyncast_pin_box<T: ?Sized + Dyncast>(
+        self: Pin<Box<Self>>,
+    ) -> Result<Pin<Box<T>>, Pin<Box<Self>>>;
+}
+
+impl<T: ?Sized + DyncastSource> DyncastPinExt for T {
+    #[inline(always)]
+    fn dyncast_pin<D: ?Sized + Dyncast>(self: Pin<&Self>) -> Option<Pin<&D>> {
+        D::dyncast_from_pin(self)
+    }
+
+    #[inline(always)]
+    fn dyncast_pin_mut<D: ?Sized + Dyncast>(self: Pin<&mut Self>) -> Option<Pin<&mut D>> {
+        D::dyncast_from_pin_mut(self)
+    }
+
+    #[inline(always)]
+    fn dyncast_pin_box<D: ?Sized + Dyncast>(
+        self: Pin<Box<Self>>,
+    ) -> Result<Pin<Box<D>>, Pin<Box<Self>>> {
+        D::dyncast_from_pin_box(self)
+    }
+}
+
+/// Provides the shared-ownership conversion [`dyncast_rc`](`DyncastRcExt::dyncast_rc`).
+///
+/// The reference count is preserved, both pointers share the same allocation.
+pub trait DyncastRcExt {
+    fn dyncast_rc<T: ?Sized + Dyncast>(self: Rc<Self>) -> Result<Rc<T>, Rc<Self>>;
+}
+
+impl<T: ?Sized + DyncastSource> DyncastRcExt for T {
+    #[inline(always)]
+    fn dyncast_rc<D: ?Sized + Dyncast>(self: Rc<Self>) -> Result<Rc<D>, Rc<Self>> {
+        D::dyncast_from_rc(self)
+    }
+}
+
+/// Provides the shared-ownership conversion [`dyncast_arc`](`DyncastArcExt::dyncast_arc`).
+///
+/// The reference count is preserved, both pointers share the same allocation.
+pub trait DyncastArcExt {
+    fn dyncast_arc<T: ?Sized + Dyncast>(self: Arc<Self>) -> Result<Arc<T>, Arc<Self>>;
+}
+
+impl<T: ?Sized + DyncastSource> DyncastArcExt for T {
+    #[inline(always)]
+    fn dyncast_arc<D: ?Sized + Dyncast>(self: Arc<Self>) -> Result<Arc<D>, Arc<Self>> {
+        D::dyncast_from_arc(self)
+    }
+}
+
+/// Provides the conversion [`dyncast_weak`](`DyncastWeakExt::dyncast_weak`) for
+/// [`rc::Weak`] and [`sync::Weak`].
+///
+/// The pointee has to be alive for the cast to succeed.
+pub trait DyncastWeakExt: Sized {
+    type Weak<T: ?Sized>;
+
+    fn dyncast_weak<T: ?Sized + Dyncast>(self) -> Result<Self::Weak<T>, Self>;
+}
+
+impl<T: ?Sized + DyncastSource> DyncastWeakExt for rc::Weak<T> {
+    type Weak<D: ?Sized> = rc::Weak<D>;
+
+    #[inline(always)]
+    fn dyncast_weak<D: ?Sized + Dyncast>(self) -> Result<rc::Weak<D>, Self> {
+        D::dyncast_from_rc_weak(self)
+    }
+}
+
+impl<T: ?Sized + DyncastSource> DyncastWeakExt for sync::Weak<T> {
+    type Weak<D: ?Sized> = sync::Weak<D>;
+
+    #[inline(always)]
+    fn dyncast_weak<D: ?Sized + Dyncast>(self) -> Result<sync::Weak<D>, Self> {
+        D::dyncast_from_arc_weak(self)
+    }
 }
@@ -1,13 +1,40 @@
 pub use std::any::TypeId;
-use std::cell::UnsafeCell;
+use std::{borrow::Cow, cell::UnsafeCell};
 
 pub use crate::map::LazyTypeMap;
-pub use crate::Dyncast;
+pub use crate::{registry::TraitInfo, Dyncast};
 
 pub mod ptr {
     pub use crate::ptr::*;
 }
 
+/// Hidden supertrait of every dyncast trait, it exposes the [`TypeId`] and the name of the
+/// concrete type through the trait's vtable.
+///
+/// The blanket impl is the only impl, which makes it impossible to override for a specific type.
+pub trait SourceHook {
+    fn __dyncast_source_type_id(&self) -> TypeId
+    where
+        Self: 'static;
+
+    fn __dyncast_source_type_name(&self) -> &'static str;
+}
+
+impl<T: ?Sized> SourceHook for T {
+    #[inline(always)]
+    fn __dyncast_source_type_id(&self) -> TypeId
+    where
+        Self: 'static,
+    {
+        TypeId::of::<T>()
+    }
+
+    #[inline(always)]
+    fn __dyncast_source_type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+}
+
 pub type Entry = SyncUnsafeCell<unsafe fn() -> Descriptor>;
 
 #[repr(transparent)]
@@ -28,42 +55,277 @@ impl<T> SyncUnsafeCell<T> {
 
 unsafe impl<T: Sync> Sync for SyncUnsafeCell<T> {}
 
+/// The auto traits a registered type implements.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AutoTraits(u8);
+
+impl AutoTraits {
+    pub const NONE: Self = Self(0);
+    pub const SEND: Self = Self(1 << 0);
+    pub const SYNC: Self = Self(1 << 1);
+    pub const UNPIN: Self = Self(1 << 2);
+
+    #[inline]
+    pub const fn probe(send: bool, sync: bool, unpin: bool) -> Self {
+        let mut bits = 0;
+        if send {
+            bits |= Self::SEND.0;
+        }
+        if sync {
+            bits |= Self::SYNC.0;
+        }
+        if unpin {
+            bits |= Self::UNPIN.0;
+        }
+        Self(bits)
+    }
+
+    #[inline]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// Detects auto trait implementations of concrete types.
+///
+/// The inherent constants take precedence over the ones of the fallback traits whenever their
+/// bounds hold, which only works if `T` is a concrete type.
+pub mod probe {
+    use std::marker::PhantomData;
+
+    pub struct Probe<T: ?Sized>(PhantomData<T>);
+
+    pub trait NotSend {
+        const SEND: bool = false;
+    }
+
+    pub trait NotSync {
+        const SYNC: bool = false;
+    }
+
+    pub trait NotUnpin {
+        const UNPIN: bool = false;
+    }
+
+    impl<T: ?Sized> NotSend for Probe<T> {}
+    impl<T: ?Sized> NotSync for Probe<T> {}
+    impl<T: ?Sized> NotUnpin for Probe<T> {}
+
+    impl<T: ?Sized + Send> Probe<T> {
+        pub const SEND: bool = true;
+    }
+
+    impl<T: ?Sized + Sync> Probe<T> {
+        pub const SYNC: bool = true;
+    }
+
+    impl<T: ?Sized + Unpin> Probe<T> {
+        pub const UNPIN: bool = true;
+    }
+
+    /// Detects which supertraits of a dyncast trait are dyncast traits themselves, `D` is the
+    /// `dyn Supertrait` and `T` a type implementing it.
+    ///
+    /// Unlike [`Probe`] this is used in generic contexts, where the bounds of the inherent
+    /// constants are proven by the supertrait bounds of the trait.
+    pub struct Supertrait<D: ?Sized, T: ?Sized = ()>(PhantomData<(*const D, *const T)>);
+
+    pub trait NotDyncast {
+        const TRAIT_INFO: Option<fn() -> super::TraitInfo> = None;
+        const DESCRIPTOR: Option<fn() -> super::Descriptor> = None;
+        const SEALED: bool = false;
+    }
+
+    impl<D: ?Sized, T: ?Sized> NotDyncast for Supertrait<D, T> {}
+
+    impl<D: ?Sized + super::Dyncast, T: ?Sized> Supertrait<D, T> {
+        pub const TRAIT_INFO: Option<fn() -> super::TraitInfo> = Some(D::dyncast_trait_info);
+        pub const SEALED: bool = D::SEALED;
+    }
+
+    impl<D: ?Sized + super::DescriptorOf<T>, T> Supertrait<D, T> {
+        pub const DESCRIPTOR: Option<fn() -> super::Descriptor> = Some(D::descriptor);
+    }
+
+    /// Tells whether the crate defining a dyncast trait is the current one. The `dyn Trait` of
+    /// every dyncast trait shadows `DYNCAST_LOCAL` with a `pub(crate)` inherent constant, which
+    /// isn't visible in other crates.
+    pub trait Foreign {
+        const DYNCAST_LOCAL: bool = false;
+    }
+
+    impl<T: ?Sized> Foreign for T {}
+}
+
 #[derive(Copy, Clone)]
 pub struct PartialDescriptor {
-    pub(crate) attach_vtable_fn: *const (),
+    pub(crate) vtable: *const (),
+    pub(crate) auto_traits: AutoTraits,
 }
 
 impl PartialDescriptor {
     #[inline]
-    pub unsafe fn attach_vtable_fn<T: ?Sized>(&self) -> unsafe fn(*const ()) -> *const T {
-        std::mem::transmute(self.attach_vtable_fn)
+    pub fn auto_traits(&self) -> AutoTraits {
+        self.auto_traits
+    }
+
+    /// # Safety
+    ///
+    /// `T` must be the dyn trait this descriptor was registered for, optionally with additional
+    /// auto traits, and `ptr` must point to a value of the type this descriptor was registered
+    /// with.
+    #[inline]
+    pub unsafe fn attach_vtable<T: ?Sized>(&self, ptr: *const ()) -> *const T {
+        crate::ptr::from_raw_parts(ptr, self.vtable)
+    }
+
+    /// # Safety
+    ///
+    /// See [`attach_vtable`](`PartialDescriptor::attach_vtable`).
+    #[inline]
+    pub unsafe fn attach_vtable_mut<T: ?Sized>(&self, ptr: *mut ()) -> *mut T {
+        crate::ptr::from_raw_parts_mut(ptr, self.vtable)
     }
 }
 
 unsafe impl Send for PartialDescriptor {}
 unsafe impl Sync for PartialDescriptor {}
 
+/// Describes the site of a registering impl.
+///
+/// # Safety
+///
+/// `AUTO_TRAITS` must only contain auto traits the registered type implements.
+pub unsafe trait Registration {
+    const AUTO_TRAITS: AutoTraits;
+    const MODULE_PATH: &'static str;
+    const FILE: &'static str;
+    const LINE: u32;
+}
+
+/// Implemented by `#[dyncast]` impls on the `dyn Trait` for every registered type `T`.
+///
+/// Traits declared with `#[dyncast(strict)]` require it through their provider trait, so an impl
+/// without `#[dyncast]` doesn't compile. The `dyn Trait` is the self type so that the orphan rules
+/// allow it for blanket impls too.
+///
+/// # Safety
+///
+/// Must only be implemented by the `dyncast` proc-macro.
+pub unsafe trait Registered<T: ?Sized> {}
+
+/// Implemented by every `dyn Trait` of a dyncast trait for the types implementing the trait.
+///
+/// # Safety
+///
+/// Must only be implemented by the `dyncast` proc-macro and [`register_trait`](crate::register_trait).
+pub unsafe trait DescriptorOf<T> {
+    fn descriptor() -> Descriptor;
+}
+
+/// Implemented by every registration for the registered type, `D` is the `dyn Trait` and `R` the
+/// [`Registration`]. `descriptor` is the function the registration points to. The impl isn't
+/// generic, so that the symbol of `descriptor` names the type and the trait even with the legacy
+/// symbol mangling.
+///
+/// # Safety
+///
+/// Must only be implemented by the `dyncast` proc-macro.
+pub unsafe trait RegistrationEntry<D: ?Sized, R> {
+    fn descriptor() -> Descriptor;
+}
+
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn trait_info(
+    type_id: TypeId,
+    type_name: &'static str,
+    module_path: &'static str,
+    file: &'static str,
+    line: u32,
+    generic_args: Vec<Cow<'static, str>>,
+    sealed: bool,
+    supertraits: Vec<TraitInfo>,
+) -> TraitInfo {
+    TraitInfo {
+        type_id,
+        type_name,
+        module_path,
+        file,
+        line,
+        generic_args,
+        sealed,
+        supertraits,
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct Descriptor {
     pub(crate) self_type_id: TypeId,
+    pub(crate) self_type_name: &'static str,
     pub(crate) dyn_trait_id: TypeId,
-    pub(crate) attach_vtable_fn: *const (),
+    pub(crate) dyn_trait_info: fn() -> TraitInfo,
+    pub(crate) vtable: *const (),
+    pub(crate) auto_traits: AutoTraits,
+    pub(crate) module_path: &'static str,
+    pub(crate) file: &'static str,
+    pub(crate) line: u32,
+    pub(crate) supertraits: fn() -> Vec<Descriptor>,
 }
 
 unsafe impl Send for Descriptor {}
 unsafe impl Sync for Descriptor {}
 
 impl Descriptor {
+    /// # Safety
+    ///
+    /// `vtable` must be the vtable of the dyn trait identified by `dyn_trait_id` for the type
+    /// identified by `self_type_id`.
     #[inline]
-    pub unsafe fn new<T: ?Sized>(
+    pub unsafe fn new(
         self_type_id: TypeId,
+        self_type_name: &'static str,
         dyn_trait_id: TypeId,
-        attach_vtable_fn: unsafe fn(*const ()) -> *const T,
+        dyn_trait_info: fn() -> TraitInfo,
+        vtable: *const (),
     ) -> Self {
         Self {
             self_type_id,
+            self_type_name,
             dyn_trait_id,
-            attach_vtable_fn: attach_vtable_fn as *const (),
+            dyn_trait_info,
+            vtable,
+            auto_traits: AutoTraits::NONE,
+            module_path: "",
+            file: "",
+            line: 0,
+            supertraits: Vec::new,
+        }
+    }
+
+    /// Adds the descriptors of the same type for the dyncast supertraits of the trait. They are
+    /// registered along with this one.
+    #[inline]
+    pub fn with_supertraits(self, supertraits: fn() -> Vec<Descriptor>) -> Self {
+        Self {
+            supertraits,
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn with_registration<R: Registration>(self) -> Self {
+        Self {
+            auto_traits: R::AUTO_TRAITS,
+            module_path: R::MODULE_PATH,
+            file: R::FILE,
+            line: R::LINE,
+            ..self
         }
     }
 }
@@ -49,3 +49,20 @@ pub unsafe fn from_raw_parts<T: ?Sized>(data_pointer: *const (), vtable: *const
         .const_ptr
     }
 }
+
+/// # Safety
+///
+/// `T` must be a dyn trait.
+#[inline(always)]
+pub unsafe fn from_raw_parts_mut<T: ?Sized>(data_pointer: *mut (), vtable: *const ()) -> *mut T {
+    assert!(Inspect::<T>::IS_DYN_TRAIT);
+    unsafe {
+        PtrRepr {
+            components: PtrComponents {
+                data_pointer: data_pointer.cast_const(),
+                vtable,
+            },
+        }
+        .mut_ptr
+    }
+}
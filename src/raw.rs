@@ -0,0 +1,71 @@
+//! Casting of type-erased raw pointers.
+//!
+//! These functions are meant for storage that keeps track of values as `(TypeId, *const ())`
+//! pairs instead of `&dyn Any`, like arenas or FFI handles.
+//!
+//! ```
+//! use std::any::TypeId;
+//!
+//! use dyncast::{dyncast, raw};
+//!
+//! #[dyncast]
+//! trait Foo {
+//!     fn foo(&self) -> u32;
+//! }
+//!
+//! #[dyncast]
+//! impl Foo for u32 {
+//!     fn foo(&self) -> u32 {
+//!         *self
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let value = 42u32;
+//!     let ptr = &value as *const u32 as *const ();
+//!
+//!     let foo = unsafe { raw::cast_ptr::<dyn Foo>(TypeId::of::<u32>(), ptr) }.unwrap();
+//!     assert_eq!(unsafe { foo.as_ref() }.foo(), 42);
+//! }
+//! ```
+
+use std::{any::TypeId, ptr::NonNull};
+
+use crate::Dyncast;
+
+/// Attaches the vtable of `T` to `ptr`, if the type identified by `type_id` has been registered
+/// for `T`.
+///
+/// Returns `None` if `ptr` is null or if there is no such registration.
+///
+/// # Safety
+///
+/// `ptr` must either be null or point to a value of the type identified by `type_id`. The
+/// returned pointer must only be used for shared access.
+#[inline]
+pub unsafe fn cast_ptr<T: ?Sized + Dyncast>(type_id: TypeId, ptr: *const ()) -> Option<NonNull<T>> {
+    if ptr.is_null() {
+        return None;
+    }
+    let descriptor = T::dyncast_descriptor(type_id)?;
+    NonNull::new(unsafe { descriptor.attach_vtable::<T>(ptr) }.cast_mut())
+}
+
+/// Mutable counterpart of [`cast_ptr`], the returned pointer keeps the provenance of `ptr`.
+///
+/// Returns `None` if `ptr` is null or if there is no such registration.
+///
+/// # Safety
+///
+/// `ptr` must either be null or point to a value of the type identified by `type_id`.
+#[inline]
+pub unsafe fn cast_ptr_mut<T: ?Sized + Dyncast>(
+    type_id: TypeId,
+    ptr: *mut (),
+) -> Option<NonNull<T>> {
+    if ptr.is_null() {
+        return None;
+    }
+    let descriptor = T::dyncast_descriptor(type_id)?;
+    NonNull::new(unsafe { descriptor.attach_vtable_mut::<T>(ptr) })
+}
@@ -0,0 +1,272 @@
+//! Queries against the registry of `(dyn Trait, Self)` pairs collected from every [`dyncast`]
+//! annotated impl.
+//!
+//! [`dyncast`]: macro@crate::dyncast
+
+use std::{
+    any::{Any, TypeId},
+    borrow::Cow,
+};
+
+use crate::{global::Global, Dyncast};
+
+#[cfg(feature = "dump")]
+mod dump;
+
+#[cfg(feature = "dump")]
+pub use self::dump::{dump, dump_dot};
+
+/// Describes a concrete type that has been registered for a dyncast trait.
+///
+/// The source location refers to the [`dyncast`](macro@crate::dyncast) annotated impl the type was
+/// registered with.
+#[derive(Clone, Debug)]
+pub struct TypeInfo {
+    pub(crate) type_id: TypeId,
+    pub(crate) type_name: &'static str,
+    pub(crate) module_path: &'static str,
+    pub(crate) file: &'static str,
+    pub(crate) line: u32,
+}
+
+impl TypeInfo {
+    /// The [`TypeId`] of the type.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The name of the type as returned by [`std::any::type_name`].
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The module path of the registering impl, as returned by [`module_path!`].
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    /// The file of the registering impl, as returned by [`file!`].
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// The line of the registering impl, as returned by [`line!`].
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+}
+
+/// Describes a dyncast trait, in the form of its `dyn Trait`.
+///
+/// The source location refers to the [`dyncast`](macro@crate::dyncast) annotated trait definition.
+#[derive(Clone, Debug)]
+pub struct TraitInfo {
+    pub(crate) type_id: TypeId,
+    pub(crate) type_name: &'static str,
+    pub(crate) module_path: &'static str,
+    pub(crate) file: &'static str,
+    pub(crate) line: u32,
+    pub(crate) generic_args: Vec<Cow<'static, str>>,
+    pub(crate) sealed: bool,
+    pub(crate) supertraits: Vec<TraitInfo>,
+}
+
+impl TraitInfo {
+    /// The [`TypeId`] of the `dyn Trait`.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The name of the `dyn Trait` as returned by [`std::any::type_name`].
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The module path of the trait definition, as returned by [`module_path!`].
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    /// The file of the trait definition, as returned by [`file!`].
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// The line of the trait definition, as returned by [`line!`].
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// The generic arguments the trait has been instantiated with, in declaration order. Type
+    /// arguments are given by their name, const arguments by their value, e.g. `["u8", "4"]` for
+    /// `dyn Trait<u8, 4>`.
+    pub fn generic_args(&self) -> &[Cow<'static, str>] {
+        &self.generic_args
+    }
+
+    /// Whether the trait has been declared with `#[dyncast(sealed)]` or has a sealed supertrait.
+    /// Registrations of sealed traits from other crates don't compile.
+    pub fn is_sealed(&self) -> bool {
+        self.sealed
+    }
+
+    /// The direct supertraits that are dyncast traits themselves, in declaration order.
+    pub fn supertraits(&self) -> &[TraitInfo] {
+        &self.supertraits
+    }
+}
+
+/// Checks whether the type identified by `type_id` has been registered for `T`, without
+/// requiring a value of that type.
+///
+/// ```
+/// use std::any::TypeId;
+///
+/// use dyncast::dyncast;
+///
+/// #[dyncast]
+/// trait Foo {}
+///
+/// #[dyncast]
+/// impl Foo for () {}
+///
+/// fn main() {
+///     assert!(dyncast::implements::<dyn Foo>(TypeId::of::<()>()));
+///     assert!(!dyncast::implements::<dyn Foo>(TypeId::of::<u8>()));
+/// }
+/// ```
+#[inline]
+pub fn implements<T: ?Sized + Dyncast>(type_id: TypeId) -> bool {
+    T::dyncast_descriptor(type_id).is_some()
+}
+
+/// Checks whether `S` has been registered for `T`.
+///
+/// This is a shorthand for [`implements::<T>(TypeId::of::<S>())`](`implements`).
+#[inline]
+pub fn implements_type<S: ?Sized + Any, T: ?Sized + Dyncast>() -> bool {
+    implements::<T>(TypeId::of::<S>())
+}
+
+/// Returns every type that has been registered for `T`.
+///
+/// The types are ordered by their [`type_name`](`TypeInfo::type_name`), types with the same name
+/// are ordered by their [`TypeId`]. This order is stable for a given binary.
+///
+/// ```
+/// use dyncast::dyncast;
+///
+/// #[dyncast]
+/// trait Format {}
+///
+/// struct Json;
+///
+/// #[dyncast]
+/// impl Format for Json {}
+///
+/// struct Csv;
+///
+/// #[dyncast]
+/// impl Format for Csv {}
+///
+/// fn main() {
+///     let names = dyncast::implementors::<dyn Format>()
+///         .map(|implementor| implementor.type_name())
+///         .collect::<Vec<_>>();
+///     assert_eq!(names, [std::any::type_name::<Csv>(), std::any::type_name::<Json>()]);
+/// }
+/// ```
+pub fn implementors<T: ?Sized + Dyncast>() -> impl Iterator<Item = &'static TypeInfo> {
+    Global::singleton()
+        .implementors
+        .get(&T::dyncast_trait_id())
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter(|implementor| implements::<T>(implementor.type_id))
+}
+
+/// Returns every dyncast trait the type identified by `type_id` has been registered for.
+///
+/// The traits are ordered by their [`type_name`](`TraitInfo::type_name`), traits with the same
+/// name are ordered by their [`TypeId`]. Auto trait combinations like `dyn Trait + Send` are not
+/// listed separately.
+///
+/// ```
+/// use std::any::TypeId;
+///
+/// use dyncast::dyncast;
+///
+/// #[dyncast]
+/// trait Foo {}
+///
+/// #[dyncast]
+/// trait Bar {}
+///
+/// #[dyncast]
+/// impl Foo for () {}
+///
+/// #[dyncast]
+/// impl Bar for () {}
+///
+/// fn main() {
+///     let names = dyncast::traits_of(TypeId::of::<()>())
+///         .map(|info| info.type_name())
+///         .collect::<Vec<_>>();
+///     assert_eq!(names, [std::any::type_name::<dyn Bar>(), std::any::type_name::<dyn Foo>()]);
+/// }
+/// ```
+pub fn traits_of(type_id: TypeId) -> impl Iterator<Item = &'static TraitInfo> {
+    let global = Global::singleton();
+    global
+        .traits
+        .get(&type_id)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|dyn_trait_id| &global.trait_infos[dyn_trait_id])
+}
+
+/// Returns every dyncast supertrait of `T`, including the supertraits of supertraits.
+///
+/// The traits are listed depth-first in declaration order, every trait is only listed once. Types
+/// registered for `T` are registered for all of them as well, so a `&dyn T` can be upcast with
+/// [`dyncast_to`](`crate::DyncastExt::dyncast_to`).
+///
+/// ```
+/// use dyncast::dyncast;
+///
+/// #[dyncast]
+/// trait Shape {}
+///
+/// #[dyncast]
+/// trait Polygon: Shape {}
+///
+/// #[dyncast]
+/// trait Square: Polygon {}
+///
+/// fn main() {
+///     let names = dyncast::supertraits_of::<dyn Square>()
+///         .iter()
+///         .map(|info| info.type_name())
+///         .collect::<Vec<_>>();
+///     assert_eq!(names, [std::any::type_name::<dyn Polygon>(), std::any::type_name::<dyn Shape>()]);
+/// }
+/// ```
+pub fn supertraits_of<T: ?Sized + Dyncast>() -> Vec<TraitInfo> {
+    fn collect(info: &TraitInfo, supertraits: &mut Vec<TraitInfo>) {
+        for supertrait in &info.supertraits {
+            if supertraits
+                .iter()
+                .all(|collected| collected.type_id != supertrait.type_id)
+            {
+                supertraits.push(supertrait.clone());
+                collect(supertrait, supertraits);
+            }
+        }
+    }
+
+    let mut supertraits = Vec::new();
+    collect(&T::dyncast_trait_info(), &mut supertraits);
+    supertraits
+}
@@ -0,0 +1,156 @@
+use std::fmt::Write;
+
+use crate::{
+    global::Global,
+    registry::{TraitInfo, TypeInfo},
+};
+
+fn traits(global: &'static Global) -> Vec<(&'static TraitInfo, &'static [TypeInfo])> {
+    let mut traits = global
+        .trait_infos
+        .iter()
+        .map(|(dyn_trait_id, info)| {
+            let implementors = global
+                .implementors
+                .get(dyn_trait_id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            (info, implementors)
+        })
+        .collect::<Vec<_>>();
+    traits.sort_unstable_by_key(|(info, _)| (info.type_name, info.type_id));
+    traits
+}
+
+fn write_json_str(out: &mut String, val: &str) {
+    out.push('"');
+    for ch in val.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch.is_control() => {
+                let _ = write!(out, "\\u{:04x}", ch as u32);
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+fn write_json_location(out: &mut String, module_path: &str, file: &str, line: u32) {
+    out.push_str("\"module_path\":");
+    write_json_str(out, module_path);
+    out.push_str(",\"file\":");
+    write_json_str(out, file);
+    let _ = write!(out, ",\"line\":{line}");
+}
+
+/// Serializes every registered trait and its implementors to JSON.
+///
+/// The output has the following shape, traits are ordered by their name and implementors in the
+/// order of [`implementors`](crate::implementors):
+///
+/// ```json
+/// {
+///   "traits": [
+///     {
+///       "name": "dyn app::Format",
+///       "module_path": "app",
+///       "file": "src/main.rs",
+///       "line": 4,
+///       "generic_args": [],
+///       "sealed": false,
+///       "implementors": [
+///         { "name": "app::Json", "module_path": "app", "file": "src/main.rs", "line": 9 }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Only traits with at least one registered type are known to the registry.
+pub fn dump() -> String {
+    let mut out = String::from("{\"traits\":[");
+
+    for (idx, (info, implementors)) in traits(Global::singleton()).into_iter().enumerate() {
+        if idx != 0 {
+            out.push(',');
+        }
+
+        out.push_str("{\"name\":");
+        write_json_str(&mut out, info.type_name);
+        out.push(',');
+        write_json_location(&mut out, info.module_path, info.file, info.line);
+
+        out.push_str(",\"generic_args\":[");
+        for (idx, generic_arg) in info.generic_args.iter().enumerate() {
+            if idx != 0 {
+                out.push(',');
+            }
+            write_json_str(&mut out, generic_arg);
+        }
+
+        let _ = write!(out, "],\"sealed\":{},\"implementors\":[", info.sealed);
+        for (idx, implementor) in implementors.iter().enumerate() {
+            if idx != 0 {
+                out.push(',');
+            }
+            out.push_str("{\"name\":");
+            write_json_str(&mut out, implementor.type_name);
+            out.push(',');
+            write_json_location(
+                &mut out,
+                implementor.module_path,
+                implementor.file,
+                implementor.line,
+            );
+            out.push('}');
+        }
+        out.push_str("]}");
+    }
+
+    out.push_str("]}");
+    out
+}
+
+fn write_dot_id(out: &mut String, val: &str) {
+    out.push('"');
+    for ch in val.chars() {
+        if ch == '"' || ch == '\\' {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out.push('"');
+}
+
+/// Renders every registered trait and its implementors as a Graphviz DOT graph.
+///
+/// Traits are drawn as boxes, every registered type has an edge to each trait it has been
+/// registered for.
+pub fn dump_dot() -> String {
+    let traits = traits(Global::singleton());
+    let mut out = String::from("digraph dyncast {\n    rankdir=LR;\n");
+
+    for (info, _) in &traits {
+        out.push_str("    ");
+        write_dot_id(&mut out, info.type_name);
+        out.push_str(" [shape=box];\n");
+    }
+
+    for (info, implementors) in &traits {
+        for implementor in *implementors {
+            out.push_str("    ");
+            write_dot_id(&mut out, implementor.type_name);
+            out.push_str(" -> ");
+            write_dot_id(&mut out, info.type_name);
+            out.push_str(";\n");
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
@@ -0,0 +1,124 @@
+use std::any::Any;
+
+use dyncast::{dyncast, DyncastExt};
+
+#[dyncast]
+trait Source {
+    type Item;
+
+    fn next(&mut self) -> Option<Self::Item>;
+}
+
+struct Counter(u32);
+
+#[dyncast]
+impl Source for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.0 += 1;
+        Some(self.0)
+    }
+}
+
+struct Words(Vec<String>);
+
+#[dyncast]
+impl Source for Words {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.0.pop()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Itself;
+
+#[dyncast]
+impl Source for Itself {
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self> {
+        Some(Itself)
+    }
+}
+
+struct Repeat<T>(T);
+
+#[dyncast(instantiate(Repeat<u32>, Repeat<char>))]
+impl<T: Clone> Source for Repeat<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        Some(self.0.clone())
+    }
+}
+
+#[dyncast(strict)]
+trait Lookup<K> {
+    type Value;
+
+    fn get(&self, key: K) -> Option<Self::Value>;
+}
+
+struct Squares;
+
+#[dyncast]
+impl Lookup<u8> for Squares {
+    type Value = u16;
+
+    fn get(&self, key: u8) -> Option<u16> {
+        Some(u16::from(key) * u16::from(key))
+    }
+}
+
+#[test]
+fn keyed_per_binding() {
+    let mut counter = Counter(0);
+    let source = (&mut counter as &mut dyn Any)
+        .dyncast_to_mut::<dyn Source<Item = u32>>()
+        .unwrap();
+    assert_eq!(source.next(), Some(1));
+    assert!((&counter as &dyn Any)
+        .dyncast_to::<dyn Source<Item = String>>()
+        .is_none());
+
+    let mut words = Words(vec![String::from("boba")]);
+    let source = (&mut words as &mut dyn Any)
+        .dyncast_to_mut::<dyn Source<Item = String>>()
+        .unwrap();
+    assert_eq!(source.next().as_deref(), Some("boba"));
+    assert!((&words as &dyn Any)
+        .dyncast_to::<dyn Source<Item = u32>>()
+        .is_none());
+
+    let mut itself = Itself;
+    let source = (&mut itself as &mut dyn Any)
+        .dyncast_to_mut::<dyn Source<Item = Itself>>()
+        .unwrap();
+    assert_eq!(source.next(), Some(Itself));
+}
+
+#[test]
+fn generic_impl() {
+    let mut repeat = Repeat('x');
+    let source = (&mut repeat as &mut dyn Any)
+        .dyncast_to_mut::<dyn Source<Item = char>>()
+        .unwrap();
+    assert_eq!(source.next(), Some('x'));
+    assert!((&Repeat(1u32) as &dyn Any)
+        .dyncast_to::<dyn Source<Item = u32>>()
+        .is_some());
+}
+
+#[test]
+fn generic_trait() {
+    let lookup = (&Squares as &dyn Any)
+        .dyncast_to::<dyn Lookup<u8, Value = u16>>()
+        .unwrap();
+    assert_eq!(lookup.get(3), Some(9));
+    assert!((&Squares as &dyn Any)
+        .dyncast_to::<dyn Lookup<u8, Value = u8>>()
+        .is_none());
+}
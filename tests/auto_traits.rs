@@ -0,0 +1,94 @@
+use std::{any::Any, cell::Cell, marker::PhantomPinned, rc::Rc};
+
+use dyncast::{dyncast, DyncastExt};
+
+#[dyncast]
+trait Job {
+    fn run(&self) -> usize;
+}
+
+struct Plain;
+
+#[dyncast]
+impl Job for Plain {
+    fn run(&self) -> usize {
+        1
+    }
+}
+
+struct Local(Rc<usize>);
+
+#[dyncast]
+impl Job for Local {
+    fn run(&self) -> usize {
+        *self.0
+    }
+}
+
+struct Shared(Cell<usize>);
+
+#[dyncast]
+impl Job for Shared {
+    fn run(&self) -> usize {
+        self.0.get()
+    }
+}
+
+struct Pinned(PhantomPinned);
+
+#[dyncast]
+impl Job for Pinned {
+    fn run(&self) -> usize {
+        0
+    }
+}
+
+#[test]
+fn send_sync() {
+    let plain = &Plain as &dyn Any;
+    assert_eq!(plain.dyncast_to::<dyn Job + Send>().unwrap().run(), 1);
+    assert_eq!(plain.dyncast_to::<dyn Job + Sync>().unwrap().run(), 1);
+    assert_eq!(
+        plain.dyncast_to::<dyn Job + Send + Sync>().unwrap().run(),
+        1
+    );
+    assert_eq!(
+        plain
+            .dyncast_to::<dyn Job + Send + Sync + Unpin>()
+            .unwrap()
+            .run(),
+        1
+    );
+
+    let local = Local(Rc::new(2));
+    let local = &local as &dyn Any;
+    assert_eq!(local.dyncast_to::<dyn Job>().unwrap().run(), 2);
+    assert!(local.dyncast_to::<dyn Job + Send>().is_none());
+    assert!(local.dyncast_to::<dyn Job + Sync>().is_none());
+
+    let shared = Shared(Cell::new(3));
+    let shared = &shared as &dyn Any;
+    assert_eq!(shared.dyncast_to::<dyn Job + Send>().unwrap().run(), 3);
+    assert!(shared.dyncast_to::<dyn Job + Sync>().is_none());
+    assert!(shared.dyncast_to::<dyn Job + Send + Sync>().is_none());
+}
+
+#[test]
+fn unpin() {
+    let pinned = &Pinned(PhantomPinned) as &dyn Any;
+    assert!(pinned.dyncast_to::<dyn Job + Send + Sync>().is_some());
+    assert!(pinned.dyncast_to::<dyn Job + Unpin>().is_none());
+    assert!(pinned.dyncast_to::<dyn Job + Send + Unpin>().is_none());
+}
+
+#[test]
+fn source() {
+    let plain = &Plain as &(dyn Job + Send + Sync);
+    assert!(plain.dyncast_to::<dyn Job + Unpin>().is_some());
+
+    let err = (&Shared(Cell::new(0)) as &dyn Any)
+        .try_dyncast_to::<dyn Job + Sync>()
+        .err()
+        .unwrap();
+    assert!(err.has_registrations());
+}
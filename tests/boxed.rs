@@ -0,0 +1,43 @@
+use std::any::Any;
+
+use dyncast::{dyncast, DyncastBoxExt};
+
+#[dyncast]
+trait Boba {
+    fn supper(&self) -> String;
+}
+
+struct A(String);
+
+#[dyncast]
+impl Boba for A {
+    fn supper(&self) -> String {
+        self.0.clone()
+    }
+}
+
+struct B;
+
+fn consume(boba: Box<dyn Boba>) -> String {
+    boba.supper()
+}
+
+#[test]
+fn boba() {
+    let a = Box::new(A("a".to_owned())) as Box<dyn Any>;
+    assert_eq!(consume(a.dyncast_box::<dyn Boba>().ok().unwrap()), "a");
+
+    let b = Box::new(B) as Box<dyn Any>;
+    let b = b.dyncast_box::<dyn Boba>().err().unwrap();
+    assert!(b.is::<B>());
+}
+
+#[test]
+fn send() {
+    let a = Box::new(A("a".to_owned())) as Box<dyn Any + Send>;
+    assert_eq!(consume(a.dyncast_box::<dyn Boba>().ok().unwrap()), "a");
+
+    let b = Box::new(B) as Box<dyn Any + Send>;
+    let b: Box<dyn Any + Send> = b.dyncast_box::<dyn Boba>().err().unwrap();
+    assert!(b.is::<B>());
+}
@@ -0,0 +1,94 @@
+use std::any::{type_name, Any, TypeId};
+
+use dyncast::{dyncast, DyncastExt};
+
+#[dyncast]
+trait Block<const N: usize> {
+    fn bytes(&self) -> [u8; N];
+}
+
+struct Word(u32);
+
+#[dyncast]
+impl Block<4> for Word {
+    fn bytes(&self) -> [u8; 4] {
+        self.0.to_le_bytes()
+    }
+}
+
+struct Zeroes<const N: usize>;
+
+#[dyncast(instantiate(Zeroes<2>, Zeroes<8>))]
+impl<const N: usize> Block<N> for Zeroes<N> {
+    fn bytes(&self) -> [u8; N] {
+        [0; N]
+    }
+}
+
+#[dyncast]
+trait Codec<T = Vec<u8>> {
+    fn encode(&self) -> T;
+}
+
+struct Json;
+
+#[dyncast]
+impl Codec for Json {
+    fn encode(&self) -> Vec<u8> {
+        b"{}".to_vec()
+    }
+}
+
+#[dyncast]
+impl Codec<[u8; 4]> for Word {
+    fn encode(&self) -> [u8; 4] {
+        self.0.to_be_bytes()
+    }
+}
+
+#[test]
+fn const_generic_trait() {
+    let word = &Word(1) as &dyn Any;
+    assert_eq!(
+        word.dyncast_to::<dyn Block<4>>().unwrap().bytes(),
+        [1, 0, 0, 0]
+    );
+    assert!(word.dyncast_to::<dyn Block<8>>().is_none());
+
+    let zeroes = &Zeroes::<8> as &dyn Any;
+    assert_eq!(zeroes.dyncast_to::<dyn Block<8>>().unwrap().bytes(), [0; 8]);
+    assert!(zeroes.dyncast_to::<dyn Block<2>>().is_none());
+    assert!((&Zeroes::<2> as &dyn Any)
+        .dyncast_to::<dyn Block<2>>()
+        .is_some());
+}
+
+#[test]
+fn defaulted_type_param() {
+    let json = &Json as &dyn Any;
+    assert_eq!(json.dyncast_to::<dyn Codec>().unwrap().encode(), b"{}");
+    assert!(json.dyncast_to::<dyn Codec<Vec<u8>>>().is_some());
+
+    let word = &Word(1) as &dyn Any;
+    assert_eq!(
+        word.dyncast_to::<dyn Codec<[u8; 4]>>().unwrap().encode(),
+        [0, 0, 0, 1]
+    );
+    assert!(word.dyncast_to::<dyn Codec>().is_none());
+}
+
+#[test]
+fn generic_args() {
+    let generic_args = |dyn_trait_id| {
+        dyncast::traits_of(TypeId::of::<Word>())
+            .find(|&info| info.type_id() == dyn_trait_id)
+            .unwrap()
+            .generic_args()
+            .to_vec()
+    };
+    assert_eq!(generic_args(TypeId::of::<dyn Block<4>>()), ["4"]);
+    assert_eq!(
+        generic_args(TypeId::of::<dyn Codec<[u8; 4]>>()),
+        [type_name::<[u8; 4]>()]
+    );
+}
@@ -0,0 +1,135 @@
+use std::any::Any;
+
+use dyncast::{dyncast, DyncastBoxExt, DyncastExt};
+
+#[dyncast]
+trait Boba {
+    fn supper(&self) -> &'static str;
+}
+
+#[dyncast]
+trait Soba {
+    fn noodles(&mut self) -> usize;
+}
+
+struct A(usize);
+
+#[dyncast]
+impl Boba for A {
+    fn supper(&self) -> &'static str {
+        "a"
+    }
+}
+
+#[dyncast]
+impl Soba for A {
+    fn noodles(&mut self) -> usize {
+        self.0 += 1;
+        self.0
+    }
+}
+
+struct B;
+
+#[dyncast]
+impl Boba for B {
+    fn supper(&self) -> &'static str {
+        "b"
+    }
+}
+
+#[test]
+fn cross_ref() {
+    let a = &A(0) as &dyn Boba;
+    let b = &B as &dyn Boba;
+
+    assert!(a.dyncast_to::<dyn Soba>().is_some());
+    assert!(b.dyncast_to::<dyn Soba>().is_none());
+
+    let soba = &A(0) as &dyn Soba;
+    assert_eq!(soba.dyncast_to::<dyn Boba>().unwrap().supper(), "a");
+}
+
+#[test]
+fn cross_mut() {
+    let mut a = A(0);
+    let a = &mut a as &mut dyn Boba;
+    assert_eq!(a.dyncast_to_mut::<dyn Soba>().unwrap().noodles(), 1);
+    assert_eq!(a.dyncast_to_mut::<dyn Soba>().unwrap().noodles(), 2);
+}
+
+#[test]
+fn cross_box() {
+    let a = Box::new(A(0)) as Box<dyn Boba>;
+    let mut soba = a.dyncast_box::<dyn Soba>().ok().unwrap();
+    assert_eq!(soba.noodles(), 1);
+
+    let b = Box::new(B) as Box<dyn Boba>;
+    let b = b.dyncast_box::<dyn Soba>().err().unwrap();
+    assert_eq!(b.supper(), "b");
+}
+
+trait Component: Any {}
+
+impl Component for A {}
+
+#[dyncast]
+trait Entity: Any {}
+
+#[dyncast]
+impl Entity for A {}
+
+#[test]
+fn any_auto_traits() {
+    let a = A(0);
+    assert!((&a as &(dyn Any + Sync)).dyncast_to::<dyn Soba>().is_some());
+    assert!((&a as &(dyn Any + Unpin))
+        .dyncast_to::<dyn Soba>()
+        .is_some());
+    assert!((&a as &(dyn Any + Send + Unpin))
+        .dyncast_to::<dyn Soba>()
+        .is_some());
+    assert!((&a as &(dyn Any + Sync + Unpin))
+        .dyncast_to::<dyn Soba>()
+        .is_some());
+    assert!((&a as &(dyn Any + Send + Sync + Unpin))
+        .dyncast_to::<dyn Soba>()
+        .is_some());
+
+    let b = Box::new(B) as Box<dyn Any + Sync>;
+    assert_eq!(b.dyncast_box::<dyn Boba>().ok().unwrap().supper(), "b");
+}
+
+#[test]
+fn any_supertrait() {
+    let component = &A(0) as &dyn Component;
+    assert_eq!(component.dyncast_to::<dyn Boba>().unwrap().supper(), "a");
+
+    let entity = &A(0) as &dyn Entity;
+    assert_eq!(entity.dyncast_to::<dyn Boba>().unwrap().supper(), "a");
+    assert!((&B as &dyn Boba).dyncast_to::<dyn Entity>().is_none());
+}
+
+#[test]
+fn borrowed() {
+    let a = A(0);
+    let any: &(dyn Any + Sync) = &a;
+    assert!(any.dyncast_to::<dyn Soba>().is_some());
+
+    let component: &dyn Component = &a;
+    assert!(component.dyncast_to::<dyn Boba>().is_some());
+
+    let boba: &dyn Boba = &a;
+    assert!(boba.dyncast_to::<dyn Soba>().is_some());
+}
+
+#[test]
+fn generic_source() {
+    fn supper<T: ?Sized + Any>(source: &T) -> Option<&'static str> {
+        source.dyncast_to::<dyn Boba>().map(|boba| boba.supper())
+    }
+
+    assert_eq!(supper(&A(0)), Some("a"));
+    assert_eq!(supper(&B as &dyn Any), Some("b"));
+    assert_eq!(supper(&B as &dyn Boba), Some("b"));
+}
@@ -0,0 +1,33 @@
+use dyncast::{dyncast, registry};
+
+#[dyncast]
+trait Format {}
+
+struct Json;
+
+#[dyncast]
+impl Format for Json {}
+
+#[test]
+fn dump() {
+    let expected = format!(
+        concat!(
+            r#"{{"traits":[{{"name":"{}","module_path":"dump","file":"tests/dump.rs","line":3,"#,
+            r#""generic_args":[],"sealed":false,"implementors":[{{"name":"{}","module_path":"dump","#,
+            r#""file":"tests/dump.rs","line":8}}]}}]}}"#
+        ),
+        std::any::type_name::<dyn Format>(),
+        std::any::type_name::<Json>(),
+    );
+    assert_eq!(registry::dump(), expected);
+}
+
+#[test]
+fn dump_dot() {
+    let expected = format!(
+        "digraph dyncast {{\n    rankdir=LR;\n    \"{trait_name}\" [shape=box];\n    \"{type_name}\" -> \"{trait_name}\";\n}}\n",
+        trait_name = std::any::type_name::<dyn Format>(),
+        type_name = std::any::type_name::<Json>(),
+    );
+    assert_eq!(registry::dump_dot(), expected);
+}
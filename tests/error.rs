@@ -0,0 +1,86 @@
+use std::any::{Any, TypeId};
+
+use dyncast::{dyncast, DyncastExt};
+
+#[dyncast]
+trait Format {
+    fn extension(&self) -> &'static str;
+}
+
+#[dyncast]
+trait Unused {}
+
+struct Json;
+
+#[dyncast]
+impl Format for Json {
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+}
+
+#[dyncast]
+trait Plugin {}
+
+struct Csv;
+
+#[dyncast]
+impl Plugin for Csv {}
+
+struct Opaque;
+
+// Implements a dyncast trait without being registered for it.
+struct Tsv;
+
+impl Plugin for Tsv {}
+
+#[test]
+fn try_dyncast_to() {
+    let json = &Json as &dyn Any;
+    assert_eq!(
+        json.try_dyncast_to::<dyn Format>().unwrap().extension(),
+        "json"
+    );
+
+    let csv = &Csv as &dyn Any;
+    let err = csv.try_dyncast_to::<dyn Format>().err().unwrap();
+    assert_eq!(err.source_type_id(), TypeId::of::<Csv>());
+    assert_eq!(err.source_type_name(), Some(std::any::type_name::<Csv>()));
+    assert_eq!(err.target_type_name(), std::any::type_name::<dyn Format>());
+    assert!(err.has_registrations());
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "`{}` is not registered for `{}`",
+            std::any::type_name::<Csv>(),
+            std::any::type_name::<dyn Format>()
+        )
+    );
+}
+
+#[test]
+fn unknown_source() {
+    let mut opaque = Opaque;
+    let opaque = &mut opaque as &mut dyn Any;
+    let err = opaque.try_dyncast_to_mut::<dyn Unused>().err().unwrap();
+    assert_eq!(err.source_type_id(), TypeId::of::<Opaque>());
+    assert_eq!(err.source_type_name(), None);
+    assert!(!err.has_registrations());
+
+    let err: Box<dyn std::error::Error> = Box::new(err);
+    assert!(err.to_string().ends_with("(no type is registered for it)"));
+}
+
+#[test]
+fn unregistered_source() {
+    let err = Opaque.try_dyncast_to::<dyn Format>().err().unwrap();
+    assert_eq!(
+        err.source_type_name(),
+        Some(std::any::type_name::<Opaque>())
+    );
+
+    let tsv = &Tsv as &dyn Plugin;
+    let err = tsv.try_dyncast_to::<dyn Format>().err().unwrap();
+    assert_eq!(err.source_type_id(), TypeId::of::<Tsv>());
+    assert_eq!(err.source_type_name(), Some(std::any::type_name::<Tsv>()));
+}
@@ -0,0 +1,13 @@
+use std::fmt::Display;
+
+use dyncast::dyncast;
+
+#[dyncast]
+trait Describe {}
+
+struct Wrapper<T>(T);
+
+#[dyncast(instantiate(Wrapper<u8>, Wrapper<Vec<u8>>))]
+impl<T: Display> Describe for Wrapper<T> {}
+
+fn main() {}
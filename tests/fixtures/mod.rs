@@ -0,0 +1,56 @@
+use std::{
+    fs,
+    path::Path,
+    process::{Command, Output},
+    sync::Mutex,
+};
+
+/// Writes the crates of `tests/fixtures/{fixture}` into a workspace of their own and runs cargo
+/// with `args` in it. Each crate is given by its name, the root file its `{name}.rs` is copied to
+/// and its dependencies besides `dyncast`.
+pub fn cargo(fixture: &str, crates: &[(&str, &str, &str)], args: &[&str]) -> Output {
+    // Tests of the same fixture would otherwise rewrite its files while it's being built.
+    static LOCK: Mutex<()> = Mutex::new(());
+    let _guard = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let tmp_dir = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let dir = tmp_dir.join(format!("{fixture}-fixture"));
+    let mut members = Vec::new();
+    for (name, file, dependencies) in crates {
+        fs::create_dir_all(dir.join(name).join("src")).unwrap();
+        fs::copy(
+            manifest_dir.join(format!("tests/fixtures/{fixture}/{name}.rs")),
+            dir.join(name).join("src").join(file),
+        )
+        .unwrap();
+        fs::write(
+            dir.join(name).join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+                 [dependencies]\ndyncast = {{ path = {manifest_dir:?} }}\n{dependencies}"
+            ),
+        )
+        .unwrap();
+        members.push(format!("{name:?}"));
+    }
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!(
+            "[workspace]\nmembers = [{}]\nresolver = \"2\"\n",
+            members.join(", ")
+        ),
+    )
+    .unwrap();
+    // The lock file of the workspace pins the dependencies to the versions already fetched for it.
+    fs::copy(manifest_dir.join("Cargo.lock"), dir.join("Cargo.lock")).unwrap();
+
+    // The fixtures share a target directory, so that `dyncast` is only built once.
+    Command::new(env!("CARGO"))
+        .args(args)
+        .args(["--offline", "--quiet", "--target-dir"])
+        .arg(tmp_dir.join("fixtures-target"))
+        .current_dir(&dir)
+        .output()
+        .unwrap()
+}
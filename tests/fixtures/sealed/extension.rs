@@ -0,0 +1,6 @@
+use dyncast::dyncast;
+use host::Protocol;
+
+// error: dyncast traits with a sealed supertrait have to be defined in its crate
+#[dyncast]
+pub trait Extension: Protocol {}
@@ -0,0 +1,15 @@
+use dyncast::dyncast;
+
+#[dyncast(sealed)]
+pub trait Protocol {
+    fn version(&self) -> u32;
+}
+
+pub struct Local;
+
+#[dyncast]
+impl Protocol for Local {
+    fn version(&self) -> u32 {
+        1
+    }
+}
@@ -0,0 +1,12 @@
+use dyncast::dyncast;
+use host::Protocol;
+
+pub struct Foreign;
+
+// error: sealed dyncast traits only accept registrations from the crate defining them
+#[dyncast]
+impl Protocol for Foreign {
+    fn version(&self) -> u32 {
+        2
+    }
+}
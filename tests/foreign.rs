@@ -0,0 +1,147 @@
+use std::{
+    any::{type_name, Any},
+    error::Error,
+    fmt::{self, Debug, Display},
+    io::{self, Write},
+};
+
+use dyncast::{dyncast, DyncastExt, TraitInfo};
+
+#[derive(Debug)]
+struct Timeout(u32);
+
+#[dyncast]
+impl Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {}s", self.0)
+    }
+}
+
+#[dyncast]
+impl Error for Timeout {}
+
+struct Secret;
+
+#[dyncast]
+impl Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(..)")
+    }
+}
+
+// A trait that can't be annotated, e.g. because it's generated by another macro.
+macro_rules! define_trait {
+    ($name:ident) => {
+        trait $name {
+            fn size(&self) -> usize;
+        }
+    };
+}
+
+define_trait!(Measure);
+
+dyncast::register_trait!(dyn Measure);
+
+#[dyncast]
+impl Measure for Secret {
+    fn size(&self) -> usize {
+        2
+    }
+}
+
+// `Write` isn't registered by dyncast, as for a trait of any other crate a local trait stands in.
+dyncast::register_trait!(trait AnyWrite: Write);
+
+dyncast::register!(dyn AnyWrite => Vec<u8>, io::Sink);
+
+#[dyncast(AnyWrite)]
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn std_traits() {
+    let timeout = &Timeout(3) as &dyn Any;
+    assert_eq!(
+        timeout.dyncast_to::<dyn Display>().unwrap().to_string(),
+        "timed out after 3s"
+    );
+    assert_eq!(
+        timeout.dyncast_to::<dyn Error>().unwrap().to_string(),
+        "timed out after 3s"
+    );
+    assert!(timeout.dyncast_to::<dyn Error + Send + Sync>().is_some());
+
+    let secret = &Secret as &dyn Any;
+    assert_eq!(
+        format!("{:?}", secret.dyncast_to::<dyn Debug>().unwrap()),
+        "Secret(..)"
+    );
+    assert!(secret.dyncast_to::<dyn Display>().is_none());
+    assert!(secret.dyncast_to::<dyn Error>().is_none());
+}
+
+#[test]
+fn implied_by_error() {
+    // `Debug` is only implemented, not registered, `Error` implies it.
+    let timeout = &Timeout(3) as &dyn Any;
+    assert_eq!(
+        format!("{:?}", timeout.dyncast_to::<dyn Debug>().unwrap()),
+        "Timeout(3)"
+    );
+
+    let supertraits = dyncast::supertraits_of::<dyn Error>()
+        .iter()
+        .map(TraitInfo::type_name)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        supertraits,
+        [type_name::<dyn Debug>(), type_name::<dyn Display>()]
+    );
+}
+
+#[test]
+fn registered_trait() {
+    let secret = &Secret as &dyn Any;
+    assert_eq!(secret.dyncast_to::<dyn Measure>().unwrap().size(), 2);
+    assert!((&Timeout(1) as &dyn Any)
+        .dyncast_to::<dyn Measure>()
+        .is_none());
+
+    let names = dyncast::implementors::<dyn Measure>()
+        .map(|implementor| implementor.type_name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, [type_name::<Secret>()]);
+}
+
+#[test]
+fn other_crate_trait() {
+    let mut buf = Vec::<u8>::new();
+    let writer = (&mut buf as &mut dyn Any)
+        .dyncast_to_mut::<dyn AnyWrite>()
+        .unwrap();
+    writer.write_all(b"boba").unwrap();
+    assert_eq!(buf, b"boba");
+
+    let mut counter = Counter(0);
+    let writer = (&mut counter as &mut dyn Any)
+        .dyncast_to_mut::<dyn AnyWrite + Send>()
+        .unwrap();
+    write!(writer, "taro").unwrap();
+    assert_eq!(counter.0, 4);
+
+    // Implementing `Write` doesn't register a type.
+    assert!((&mut io::stderr() as &mut dyn Any)
+        .dyncast_to_mut::<dyn AnyWrite>()
+        .is_none());
+    assert_eq!(dyncast::implementors::<dyn AnyWrite>().count(), 3);
+}
@@ -0,0 +1,127 @@
+use std::{any::Any, fmt::Display};
+
+use dyncast::{dyncast, DyncastExt};
+
+mod fixtures;
+
+#[dyncast]
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+struct Wrapper<T>(T);
+
+#[dyncast(instantiate(Wrapper<u8>, Wrapper<String>))]
+impl<T: Display> Describe for Wrapper<T> {
+    fn describe(&self) -> String {
+        format!("wrapper of {}", self.0)
+    }
+}
+
+#[dyncast]
+trait Convert<T> {
+    fn convert(&self) -> T;
+}
+
+struct Pair<A, B>(A, B);
+
+#[dyncast(instantiate(Pair<&'static str, u16>, self::Pair<bool, [u8; 2]>))]
+impl<A, B: Clone + 'static> Convert<B> for Pair<A, B> {
+    fn convert(&self) -> B {
+        self.1.clone()
+    }
+}
+
+#[dyncast]
+trait Number {
+    fn value(&self) -> i64;
+}
+
+#[dyncast(instantiate(u32, i16))]
+impl<T: Copy + Into<i64>> Number for T {
+    fn value(&self) -> i64 {
+        (*self).into()
+    }
+}
+
+#[dyncast(strict)]
+trait Strict {}
+
+#[dyncast(instantiate(Wrapper<()>))]
+impl<T> Strict for Wrapper<T> {}
+
+#[test]
+fn instantiations() {
+    let wrapper = &Wrapper(7u8) as &dyn Any;
+    assert_eq!(
+        wrapper.dyncast_to::<dyn Describe>().unwrap().describe(),
+        "wrapper of 7"
+    );
+
+    let wrapper = &Wrapper(String::from("boba")) as &dyn Any;
+    assert_eq!(
+        wrapper.dyncast_to::<dyn Describe>().unwrap().describe(),
+        "wrapper of boba"
+    );
+
+    let wrapper = &Wrapper(7u16) as &dyn Any;
+    assert!(wrapper.dyncast_to::<dyn Describe>().is_none());
+}
+
+#[test]
+fn generic_trait() {
+    let pair = &Pair("a", 2u16) as &dyn Any;
+    assert_eq!(pair.dyncast_to::<dyn Convert<u16>>().unwrap().convert(), 2);
+
+    let pair = &Pair(true, [1u8, 2]) as &dyn Any;
+    assert_eq!(
+        pair.dyncast_to::<dyn Convert<[u8; 2]>>().unwrap().convert(),
+        [1, 2]
+    );
+    assert!(pair.dyncast_to::<dyn Convert<u16>>().is_none());
+}
+
+#[test]
+fn blanket_impl() {
+    assert_eq!(
+        (&3u32 as &dyn Any)
+            .dyncast_to::<dyn Number>()
+            .unwrap()
+            .value(),
+        3
+    );
+    assert_eq!(
+        (&-4i16 as &dyn Any)
+            .dyncast_to::<dyn Number>()
+            .unwrap()
+            .value(),
+        -4
+    );
+    assert!((&5u8 as &dyn Any).dyncast_to::<dyn Number>().is_none());
+}
+
+#[test]
+fn strict_trait() {
+    assert!((&Wrapper(()) as &dyn Any)
+        .dyncast_to::<dyn Strict>()
+        .is_some());
+}
+
+#[test]
+fn unsatisfied_instantiation() {
+    let output = fixtures::cargo(
+        "instantiate",
+        &[("unsatisfied", "main.rs", "")],
+        &["build", "--message-format", "short"],
+    );
+    assert!(!output.status.success());
+
+    // The error points at `Wrapper<Vec<u8>>` in the attribute.
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("src/main.rs:10:36: error[E0277]: `Vec<u8>` doesn't implement"),
+        "{stderr}"
+    );
+    assert_eq!(stderr.matches("error[").count(), 1, "{stderr}");
+    assert!(!stderr.contains("DescriptorOf"), "{stderr}");
+}
@@ -0,0 +1,93 @@
+use std::any::{Any, TypeId};
+
+use dyncast::{dyncast, implements, DyncastExt};
+
+struct Node {
+    name: String,
+}
+
+#[dyncast]
+trait Visitor<'a> {
+    fn visit(&mut self, node: &'a Node);
+}
+
+#[derive(Default)]
+struct Collector<'a>(Vec<&'a str>);
+
+#[derive(Default)]
+struct Counter(usize);
+
+#[dyncast]
+impl<'a> Visitor<'a> for Counter {
+    fn visit(&mut self, _node: &'a Node) {
+        self.0 += 1;
+    }
+}
+
+#[dyncast(instantiate(Collector<'static>))]
+impl<'a, 'b> Visitor<'a> for Collector<'b> {
+    fn visit(&mut self, _node: &'a Node) {
+        self.0.push("node");
+    }
+}
+
+#[dyncast(strict)]
+trait Parser<'s, T> {
+    fn parse(&self, input: &'s str) -> T;
+}
+
+struct Length;
+
+#[dyncast]
+impl<'s> Parser<'s, usize> for Length {
+    fn parse(&self, input: &'s str) -> usize {
+        input.len()
+    }
+}
+
+struct Prefix;
+
+#[dyncast]
+impl Parser<'_, &'static str> for Prefix {
+    fn parse(&self, _input: &str) -> &'static str {
+        "prefix"
+    }
+}
+
+#[test]
+fn higher_ranked() {
+    let mut counter = Counter::default();
+    let visitor = (&mut counter as &mut dyn Any)
+        .dyncast_to_mut::<dyn for<'a> Visitor<'a>>()
+        .unwrap();
+
+    // The node doesn't outlive the visitor, this only works with the higher-ranked trait object.
+    for name in ["a", "b"] {
+        let node = Node {
+            name: name.to_owned(),
+        };
+        assert!(!node.name.is_empty());
+        visitor.visit(&node);
+    }
+    assert_eq!(counter.0, 2);
+}
+
+#[test]
+fn lifetime_not_in_self_type() {
+    assert!(implements::<dyn for<'a> Visitor<'a>>(TypeId::of::<
+        Collector<'static>,
+    >()));
+}
+
+#[test]
+fn lifetime_and_type_params() {
+    let parser = (&Length as &dyn Any)
+        .dyncast_to::<dyn for<'s> Parser<'s, usize>>()
+        .unwrap();
+    assert_eq!(parser.parse(&String::from("boba")), 4);
+
+    let parser = (&Prefix as &dyn Any)
+        .dyncast_to::<dyn for<'s> Parser<'s, &'static str>>()
+        .unwrap();
+    assert_eq!(parser.parse("boba"), "prefix");
+}
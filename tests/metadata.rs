@@ -0,0 +1,37 @@
+use std::any::TypeId;
+
+use dyncast::dyncast;
+
+#[dyncast]
+trait Convert<To: 'static> {}
+
+struct Conv;
+
+#[dyncast]
+impl Convert<String> for Conv {}
+
+#[test]
+fn type_info() {
+    let info = dyncast::implementors::<dyn Convert<String>>()
+        .next()
+        .unwrap();
+    assert_eq!(info.type_id(), TypeId::of::<Conv>());
+    assert_eq!(info.type_name(), std::any::type_name::<Conv>());
+    assert_eq!(info.module_path(), module_path!());
+    assert_eq!(info.file(), file!());
+    assert_eq!(info.line(), 10);
+}
+
+#[test]
+fn trait_info() {
+    let info = dyncast::traits_of(TypeId::of::<Conv>()).next().unwrap();
+    assert_eq!(info.type_id(), TypeId::of::<dyn Convert<String>>());
+    assert_eq!(
+        info.type_name(),
+        std::any::type_name::<dyn Convert<String>>()
+    );
+    assert_eq!(info.module_path(), module_path!());
+    assert_eq!(info.file(), file!());
+    assert_eq!(info.line(), 5);
+    assert_eq!(info.generic_args(), [std::any::type_name::<String>()]);
+}
@@ -0,0 +1,44 @@
+use std::any::Any;
+
+use dyncast::{dyncast, DyncastExt};
+
+#[dyncast]
+trait Tick {
+    fn tick(&mut self);
+
+    fn ticks(&self) -> usize;
+}
+
+struct Counter(usize);
+
+#[dyncast]
+impl Tick for Counter {
+    fn tick(&mut self) {
+        self.0 += 1;
+    }
+
+    fn ticks(&self) -> usize {
+        self.0
+    }
+}
+
+struct Idle;
+
+#[test]
+fn tick() {
+    let mut components: Vec<Box<dyn Any>> = vec![Box::new(Counter(0)), Box::new(Idle)];
+
+    for _ in 0..3 {
+        for component in &mut components {
+            if let Some(tick) = (**component).dyncast_to_mut::<dyn Tick>() {
+                tick.tick();
+            }
+        }
+    }
+
+    assert_eq!(
+        (*components[0]).dyncast_to::<dyn Tick>().unwrap().ticks(),
+        3
+    );
+    assert!((*components[1]).dyncast_to_mut::<dyn Tick>().is_none());
+}
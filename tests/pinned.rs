@@ -0,0 +1,77 @@
+use std::{any::Any, marker::PhantomPinned, pin::Pin};
+
+use dyncast::{dyncast, DyncastPinExt};
+
+#[dyncast]
+trait Machine {
+    fn step(self: Pin<&mut Self>);
+
+    fn state(&self) -> usize;
+}
+
+struct Counter {
+    state: usize,
+    _pinned: PhantomPinned,
+}
+
+impl Counter {
+    fn new() -> Self {
+        Self {
+            state: 0,
+            _pinned: PhantomPinned,
+        }
+    }
+}
+
+#[dyncast]
+impl Machine for Counter {
+    fn step(self: Pin<&mut Self>) {
+        unsafe { self.get_unchecked_mut().state += 1 };
+    }
+
+    fn state(&self) -> usize {
+        self.state
+    }
+}
+
+struct Idle;
+
+#[test]
+fn pin_ref() {
+    let counter = Box::pin(Counter::new()) as Pin<Box<dyn Any>>;
+    let machine = counter.as_ref().dyncast_pin::<dyn Machine>().unwrap();
+    assert_eq!(machine.state(), 0);
+
+    let idle = Box::pin(Idle) as Pin<Box<dyn Any>>;
+    assert!(idle.as_ref().dyncast_pin::<dyn Machine>().is_none());
+}
+
+#[test]
+fn pin_mut() {
+    let mut counter = Box::pin(Counter::new()) as Pin<Box<dyn Any>>;
+    let address = &*counter as *const dyn Any as *const ();
+
+    for _ in 0..2 {
+        counter
+            .as_mut()
+            .dyncast_pin_mut::<dyn Machine>()
+            .unwrap()
+            .step();
+    }
+
+    let machine = counter.as_mut().dyncast_pin_mut::<dyn Machine>().unwrap();
+    assert_eq!(machine.state(), 2);
+    assert_eq!(&*machine as *const dyn Machine as *const (), address);
+}
+
+#[test]
+fn pin_box() {
+    let counter = Box::pin(Counter::new()) as Pin<Box<dyn Any>>;
+    let mut machine = counter.dyncast_pin_box::<dyn Machine>().ok().unwrap();
+    machine.as_mut().step();
+    assert_eq!(machine.state(), 1);
+
+    let idle = Box::pin(Idle) as Pin<Box<dyn Any>>;
+    let idle = idle.dyncast_pin_box::<dyn Machine>().err().unwrap();
+    assert!(idle.is::<Idle>());
+}
@@ -0,0 +1,45 @@
+use std::any::TypeId;
+
+use dyncast::{dyncast, raw};
+
+#[dyncast]
+trait Slot {
+    fn get(&self) -> u64;
+
+    fn set(&mut self, value: u64);
+}
+
+struct Cell(u64);
+
+#[dyncast]
+impl Slot for Cell {
+    fn get(&self) -> u64 {
+        self.0
+    }
+
+    fn set(&mut self, value: u64) {
+        self.0 = value;
+    }
+}
+
+#[test]
+fn cast_ptr() {
+    let cell = Cell(1);
+    let ptr = &cell as *const Cell as *const ();
+
+    let slot = unsafe { raw::cast_ptr::<dyn Slot>(TypeId::of::<Cell>(), ptr) }.unwrap();
+    assert_eq!(unsafe { slot.as_ref() }.get(), 1);
+
+    assert!(unsafe { raw::cast_ptr::<dyn Slot>(TypeId::of::<u8>(), ptr) }.is_none());
+    assert!(unsafe { raw::cast_ptr::<dyn Slot>(TypeId::of::<Cell>(), std::ptr::null()) }.is_none());
+}
+
+#[test]
+fn cast_ptr_mut() {
+    let mut cell = Cell(1);
+    let ptr = &mut cell as *mut Cell as *mut ();
+
+    let mut slot = unsafe { raw::cast_ptr_mut::<dyn Slot>(TypeId::of::<Cell>(), ptr) }.unwrap();
+    unsafe { slot.as_mut() }.set(2);
+    assert_eq!(cell.0, 2);
+}
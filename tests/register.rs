@@ -0,0 +1,125 @@
+use std::{
+    any::{type_name, Any, TypeId},
+    fmt::Display,
+    rc::Rc,
+};
+
+use dyncast::{dyncast, DyncastExt};
+
+#[dyncast]
+trait Boba {
+    fn flavor(&self) -> String;
+}
+
+// Stands in for impls generated by a derive macro or another crate.
+macro_rules! impl_boba {
+    ($($ty:ty),*) => {
+        $(impl Boba for $ty {
+            fn flavor(&self) -> String {
+                stringify!($ty).to_owned()
+            }
+        })*
+    };
+}
+
+struct C<T>(T);
+
+impl_boba!(String, Rc<u8>, C<u8>, C<u16>);
+
+dyncast::register!(dyn Boba => String, Rc<u8>, C<u8>);
+
+#[dyncast]
+trait Source {
+    type Item;
+
+    fn next(&mut self) -> Option<Self::Item>;
+}
+
+impl Source for u32 {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        Some(*self)
+    }
+}
+
+dyncast::register!(dyn Source<Item = u32> => u32);
+
+#[dyncast]
+trait Visitor<'a> {
+    fn visit(&self, name: &'a str) -> usize;
+}
+
+impl<'a> Visitor<'a> for u64 {
+    fn visit(&self, name: &'a str) -> usize {
+        name.len()
+    }
+}
+
+dyncast::register!(dyn for<'a> Visitor<'a> => u64);
+
+dyncast::register!(dyn Display => char, std::net::Ipv4Addr);
+
+#[dyncast]
+trait Topping {}
+
+struct Pearl;
+
+#[dyncast]
+impl Topping for Pearl {}
+
+dyncast::register!(dyn Topping => Pearl);
+
+#[test]
+fn listed_types() {
+    let string = &String::from("taro") as &dyn Any;
+    assert_eq!(string.dyncast_to::<dyn Boba>().unwrap().flavor(), "String");
+    let c = &C(1u8) as &dyn Any;
+    assert_eq!(c.dyncast_to::<dyn Boba>().unwrap().flavor(), "C<u8>");
+
+    // Only the listed instantiations are registered.
+    assert!((&C(1u16) as &dyn Any).dyncast_to::<dyn Boba>().is_none());
+
+    let names = dyncast::implementors::<dyn Boba>()
+        .map(|implementor| implementor.type_name())
+        .collect::<Vec<_>>();
+    assert_eq!(names.len(), 3);
+    assert!(names.contains(&type_name::<Rc<u8>>()));
+}
+
+#[test]
+fn auto_traits() {
+    let string = &String::new() as &dyn Any;
+    assert!(string.dyncast_to::<dyn Boba + Send + Sync>().is_some());
+    let rc = &Rc::new(0u8) as &dyn Any;
+    assert!(rc.dyncast_to::<dyn Boba>().is_some());
+    assert!(rc.dyncast_to::<dyn Boba + Send>().is_none());
+}
+
+#[test]
+fn trait_objects() {
+    let mut value = 7u32;
+    let source = (&mut value as &mut dyn Any)
+        .dyncast_to_mut::<dyn Source<Item = u32>>()
+        .unwrap();
+    assert_eq!(source.next(), Some(7));
+
+    let visitor = (&1u64 as &dyn Any)
+        .dyncast_to::<dyn for<'a> Visitor<'a>>()
+        .unwrap();
+    assert_eq!(visitor.visit("boba"), 4);
+
+    let addr = &std::net::Ipv4Addr::LOCALHOST as &dyn Any;
+    assert_eq!(
+        addr.dyncast_to::<dyn Display>().unwrap().to_string(),
+        "127.0.0.1"
+    );
+    assert!((&'x' as &dyn Any).dyncast_to::<dyn Display>().is_some());
+}
+
+#[test]
+fn registered_twice() {
+    assert!((&Pearl as &dyn Any).dyncast_to::<dyn Topping>().is_some());
+    assert_eq!(dyncast::implementors::<dyn Topping>().count(), 1);
+    assert_eq!(dyncast::traits_of(TypeId::of::<Pearl>()).count(), 1);
+}
@@ -0,0 +1,83 @@
+use std::any::TypeId;
+
+use dyncast::dyncast;
+
+#[dyncast]
+trait Format {}
+
+#[dyncast]
+trait Unregistered {}
+
+struct Json;
+
+#[dyncast]
+impl Format for Json {}
+
+struct Csv(std::marker::PhantomData<std::rc::Rc<()>>);
+
+#[dyncast]
+impl Format for Csv {}
+
+#[test]
+fn implements() {
+    assert!(dyncast::implements::<dyn Format>(TypeId::of::<Json>()));
+    assert!(dyncast::implements::<dyn Format>(TypeId::of::<Csv>()));
+    assert!(!dyncast::implements::<dyn Format>(TypeId::of::<u8>()));
+    assert!(!dyncast::implements::<dyn Unregistered>(
+        TypeId::of::<Json>()
+    ));
+
+    assert!(dyncast::implements::<dyn Format + Send>(
+        TypeId::of::<Json>()
+    ));
+    assert!(!dyncast::implements::<dyn Format + Send>(
+        TypeId::of::<Csv>()
+    ));
+}
+
+#[test]
+fn implements_type() {
+    assert!(dyncast::implements_type::<Json, dyn Format>());
+    assert!(!dyncast::implements_type::<Json, dyn Unregistered>());
+    assert!(!dyncast::implements_type::<Csv, dyn Format + Sync>());
+}
+
+#[test]
+fn implementors() {
+    let implementors = dyncast::implementors::<dyn Format>().collect::<Vec<_>>();
+    assert_eq!(implementors.len(), 2);
+    assert_eq!(implementors[0].type_id(), TypeId::of::<Csv>());
+    assert_eq!(implementors[0].type_name(), std::any::type_name::<Csv>());
+    assert_eq!(implementors[1].type_id(), TypeId::of::<Json>());
+    assert_eq!(implementors[1].type_name(), std::any::type_name::<Json>());
+
+    let send = dyncast::implementors::<dyn Format + Send>()
+        .map(|implementor| implementor.type_id())
+        .collect::<Vec<_>>();
+    assert_eq!(send, [TypeId::of::<Json>()]);
+
+    assert_eq!(dyncast::implementors::<dyn Unregistered>().count(), 0);
+}
+
+#[dyncast]
+trait Plugin {}
+
+#[dyncast]
+impl Plugin for Json {}
+
+#[test]
+fn traits_of() {
+    let traits = dyncast::traits_of(TypeId::of::<Json>()).collect::<Vec<_>>();
+    assert_eq!(traits.len(), 2);
+    assert_eq!(traits[0].type_id(), TypeId::of::<dyn Format>());
+    assert_eq!(traits[0].type_name(), std::any::type_name::<dyn Format>());
+    assert_eq!(traits[1].type_id(), TypeId::of::<dyn Plugin>());
+    assert_eq!(traits[1].type_name(), std::any::type_name::<dyn Plugin>());
+
+    let traits = dyncast::traits_of(TypeId::of::<Csv>())
+        .map(|info| info.type_id())
+        .collect::<Vec<_>>();
+    assert_eq!(traits, [TypeId::of::<dyn Format>()]);
+
+    assert_eq!(dyncast::traits_of(TypeId::of::<u8>()).count(), 0);
+}
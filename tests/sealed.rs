@@ -0,0 +1,78 @@
+use std::any::{Any, TypeId};
+
+use dyncast::{dyncast, traits_of, DyncastExt};
+
+mod fixtures;
+
+#[dyncast(sealed)]
+trait Protocol {
+    fn version(&self) -> u32;
+}
+
+#[dyncast(strict, sealed)]
+trait Handshake {}
+
+// Sealed as well through its supertrait.
+#[dyncast]
+trait Upgrade: Protocol {}
+
+struct Local;
+
+#[dyncast]
+impl Protocol for Local {
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+#[dyncast]
+impl Handshake for Local {}
+
+#[dyncast]
+impl Upgrade for Local {}
+
+#[test]
+fn local_registration() {
+    let local = &Local as &dyn Any;
+    assert_eq!(local.dyncast_to::<dyn Protocol>().unwrap().version(), 1);
+    assert!(local.dyncast_to::<dyn Handshake>().is_some());
+
+    let info = &traits_of(TypeId::of::<Local>()).collect::<Vec<_>>();
+    assert!(info.iter().all(|info| info.is_sealed()));
+}
+
+/// Builds `crate_name` of `tests/fixtures/sealed`, which depends on `host`, and returns the errors.
+fn build_fixture(crate_name: &str) -> String {
+    let host = "host = { path = \"../host\" }\n";
+    let output = fixtures::cargo(
+        "sealed",
+        &[
+            ("host", "lib.rs", ""),
+            ("plugin", "lib.rs", host),
+            ("extension", "lib.rs", host),
+        ],
+        &["build", "-p", crate_name],
+    );
+    assert!(!output.status.success());
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn foreign_registration() {
+    let stderr = build_fixture("plugin");
+    assert!(
+        stderr.contains(
+            "sealed dyncast traits only accept registrations from the crate defining them"
+        ),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn foreign_subtrait() {
+    let stderr = build_fixture("extension");
+    assert!(
+        stderr.contains("dyncast traits with a sealed supertrait have to be defined in its crate"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,77 @@
+use std::{
+    any::Any,
+    rc::{self, Rc},
+    sync::{self, Arc},
+};
+
+use dyncast::{dyncast, DyncastArcExt, DyncastRcExt, DyncastWeakExt};
+
+#[dyncast]
+trait Service {
+    fn name(&self) -> &'static str;
+}
+
+struct Database;
+
+#[dyncast]
+impl Service for Database {
+    fn name(&self) -> &'static str {
+        "database"
+    }
+}
+
+struct Config;
+
+#[test]
+fn rc() {
+    let database = Rc::new(Database) as Rc<dyn Any>;
+    let service = database.clone().dyncast_rc::<dyn Service>().ok().unwrap();
+    assert_eq!(service.name(), "database");
+    assert_eq!(Rc::strong_count(&database), 2);
+
+    let config = Rc::new(Config) as Rc<dyn Any>;
+    let config = config.dyncast_rc::<dyn Service>().err().unwrap();
+    assert!(config.is::<Config>());
+    assert_eq!(Rc::strong_count(&config), 1);
+}
+
+#[test]
+fn arc() {
+    let database = Arc::new(Database) as Arc<dyn Any + Send + Sync>;
+    let service = database.clone().dyncast_arc::<dyn Service>().ok().unwrap();
+    assert_eq!(service.name(), "database");
+    assert_eq!(Arc::strong_count(&database), 2);
+
+    let config = Arc::new(Config) as Arc<dyn Any + Send + Sync>;
+    let config = config.dyncast_arc::<dyn Service>().err().unwrap();
+    assert!(config.is::<Config>());
+}
+
+#[test]
+fn weak() {
+    let database = Rc::new(Database) as Rc<dyn Any>;
+    let service: rc::Weak<dyn Service> = Rc::downgrade(&database)
+        .dyncast_weak::<dyn Service>()
+        .ok()
+        .unwrap();
+    assert_eq!(service.upgrade().unwrap().name(), "database");
+    assert_eq!(Rc::weak_count(&database), 1);
+
+    let database = Arc::new(Database) as Arc<dyn Any + Send + Sync>;
+    let weak = Arc::downgrade(&database);
+    drop(database);
+    let weak: sync::Weak<dyn Any + Send + Sync> = weak.dyncast_weak::<dyn Service>().err().unwrap();
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn arc_send_sync() {
+    let database = Arc::new(Database) as Arc<dyn Any + Send + Sync>;
+    let service: Arc<dyn Service + Send + Sync> = database
+        .dyncast_arc::<dyn Service + Send + Sync>()
+        .ok()
+        .unwrap();
+    std::thread::spawn(move || assert_eq!(service.name(), "database"))
+        .join()
+        .unwrap();
+}
@@ -0,0 +1,88 @@
+use std::any::Any;
+
+use dyncast::{dyncast, implementors, DyncastExt};
+
+#[dyncast(strict)]
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+#[dyncast(strict)]
+trait Named<T> {
+    fn name(&self) -> T;
+}
+
+// The provider trait of a public strict trait is one of its supertraits, it must be as public.
+#[dyncast(strict)]
+pub trait Plugin {
+    fn id(&self) -> u32;
+}
+
+#[dyncast(strict)]
+trait Num {
+    fn double(&self) -> u32;
+}
+
+struct Square(f64);
+
+#[dyncast]
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+#[dyncast]
+impl Named<&'static str> for Square {
+    fn name(&self) -> &'static str {
+        "square"
+    }
+}
+
+#[dyncast]
+impl Plugin for Square {
+    fn id(&self) -> u32 {
+        7
+    }
+}
+
+// Blanket impls of strict traits accept every type of the impl, only the instantiations are
+// registered.
+#[dyncast(instantiate(u8, u16))]
+impl<T: Copy + Into<u32>> Num for T {
+    fn double(&self) -> u32 {
+        (*self).into() * 2
+    }
+}
+
+#[test]
+fn strict_cast() {
+    let square = &Square(2.0) as &dyn Any;
+    assert_eq!(square.dyncast_to::<dyn Shape>().unwrap().area(), 4.0);
+    assert_eq!(
+        square
+            .dyncast_to::<dyn Named<&'static str>>()
+            .unwrap()
+            .name(),
+        "square"
+    );
+    assert_eq!(implementors::<dyn Shape>().count(), 1);
+    assert_eq!(square.dyncast_to::<dyn Plugin>().unwrap().id(), 7);
+}
+
+#[test]
+fn strict_blanket_impl() {
+    assert_eq!(
+        (&3u8 as &dyn Any).dyncast_to::<dyn Num>().unwrap().double(),
+        6
+    );
+    assert_eq!(
+        (&4u16 as &dyn Any)
+            .dyncast_to::<dyn Num>()
+            .unwrap()
+            .double(),
+        8
+    );
+    assert!((&5u32 as &dyn Any).dyncast_to::<dyn Num>().is_none());
+    assert_eq!(5u32.double(), 10);
+}
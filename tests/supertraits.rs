@@ -0,0 +1,156 @@
+use std::{any::type_name, fmt::Debug};
+
+use dyncast::{dyncast, DyncastBoxExt, DyncastExt, TraitInfo};
+
+#[dyncast]
+trait Shape {
+    fn name(&self) -> &'static str;
+}
+
+#[dyncast]
+trait Polygon: Shape + Debug {
+    fn corners(&self) -> usize;
+}
+
+#[dyncast]
+trait Square: Polygon + Send {}
+
+#[derive(Debug)]
+struct Tile;
+
+impl Shape for Tile {
+    fn name(&self) -> &'static str {
+        "tile"
+    }
+}
+
+impl Polygon for Tile {
+    fn corners(&self) -> usize {
+        4
+    }
+}
+
+#[dyncast]
+impl Square for Tile {}
+
+#[derive(Debug)]
+struct Triangle;
+
+#[dyncast]
+impl Shape for Triangle {
+    fn name(&self) -> &'static str {
+        "triangle"
+    }
+}
+
+#[dyncast]
+impl Polygon for Triangle {
+    fn corners(&self) -> usize {
+        3
+    }
+}
+
+#[dyncast]
+trait Scale<T: 'static> {
+    fn factor(&self) -> T;
+}
+
+#[dyncast]
+trait Resize<T: 'static>: Scale<T> {}
+
+impl Scale<u8> for Tile {
+    fn factor(&self) -> u8 {
+        2
+    }
+}
+
+#[dyncast]
+impl Resize<u8> for Tile {}
+
+#[dyncast]
+trait Observe<'a> {
+    fn observe(&mut self, value: &'a str);
+}
+
+#[dyncast]
+trait Visit<'a>: Observe<'a> {}
+
+#[derive(Default)]
+struct Log(Vec<String>);
+
+impl<'a> Observe<'a> for Log {
+    fn observe(&mut self, value: &'a str) {
+        self.0.push(value.to_owned());
+    }
+}
+
+#[dyncast]
+impl<'a> Visit<'a> for Log {}
+
+fn names(infos: &[TraitInfo]) -> Vec<&'static str> {
+    infos.iter().map(TraitInfo::type_name).collect()
+}
+
+#[test]
+fn upcast() {
+    let square: &dyn Square = &Tile;
+    let polygon = square.dyncast_to::<dyn Polygon>().unwrap();
+    assert_eq!(polygon.corners(), 4);
+    let shape = polygon.dyncast_to::<dyn Shape>().unwrap();
+    assert_eq!(shape.name(), "tile");
+    assert_eq!(square.dyncast_to::<dyn Shape>().unwrap().name(), "tile");
+
+    let square: Box<dyn Square + Send> = Box::new(Tile);
+    let shape = square.dyncast_box::<dyn Shape + Send>().ok().unwrap();
+    assert_eq!(shape.name(), "tile");
+
+    let resize: &dyn Resize<u8> = &Tile;
+    assert_eq!(resize.dyncast_to::<dyn Scale<u8>>().unwrap().factor(), 2);
+
+    let mut log = Log::default();
+    let visit: &mut dyn for<'a> Visit<'a> = &mut log;
+    visit
+        .dyncast_to_mut::<dyn for<'a> Observe<'a>>()
+        .unwrap()
+        .observe("boba");
+    assert_eq!(log.0, ["boba"]);
+}
+
+#[test]
+fn implied_registrations() {
+    let shapes = dyncast::implementors::<dyn Shape>()
+        .map(|implementor| (implementor.type_name(), implementor.line()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        shapes,
+        [(type_name::<Tile>(), 33), (type_name::<Triangle>(), 39)]
+    );
+    assert!(!dyncast::implements_type::<Triangle, dyn Square>());
+}
+
+#[test]
+fn hierarchy() {
+    assert_eq!(
+        names(&dyncast::supertraits_of::<dyn Square>()),
+        [
+            type_name::<dyn Polygon>(),
+            type_name::<dyn Shape>(),
+            type_name::<dyn Debug>()
+        ]
+    );
+    assert_eq!(
+        names(&dyncast::supertraits_of::<dyn Resize<u8>>()),
+        [type_name::<dyn Scale<u8>>()]
+    );
+    assert_eq!(
+        names(&dyncast::supertraits_of::<dyn for<'a> Visit<'a>>()),
+        [type_name::<dyn for<'a> Observe<'a>>()]
+    );
+    assert!(dyncast::supertraits_of::<dyn Shape>().is_empty());
+
+    let square = dyncast::supertraits_of::<dyn Square>();
+    assert_eq!(
+        names(square[0].supertraits()),
+        [type_name::<dyn Shape>(), type_name::<dyn Debug>()]
+    );
+}
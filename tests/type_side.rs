@@ -0,0 +1,111 @@
+use std::{
+    any::{type_name, Any},
+    fmt::{self, Debug, Display},
+};
+
+use dyncast::{dyncast, DyncastExt};
+
+#[dyncast(strict)]
+trait Boba {
+    fn flavor(&self) -> &'static str;
+}
+
+#[dyncast]
+trait Source {
+    type Item;
+
+    fn next(&mut self) -> Option<Self::Item>;
+}
+
+#[dyncast]
+trait Visitor<'a> {
+    fn visit(&self, name: &'a str) -> usize;
+}
+
+#[dyncast(Display, Debug, Boba)]
+struct Taro;
+
+impl Display for Taro {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("taro")
+    }
+}
+
+impl Debug for Taro {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Taro")
+    }
+}
+
+// `Boba` is strict, the registration at the definition satisfies it.
+impl Boba for Taro {
+    fn flavor(&self) -> &'static str {
+        "taro"
+    }
+}
+
+#[dyncast(Boba, Source<Item = u32>, for<'a> Visitor<'a>)]
+enum Counter {
+    Up(u32),
+}
+
+impl Boba for Counter {
+    fn flavor(&self) -> &'static str {
+        "counter"
+    }
+}
+
+impl Source for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let Counter::Up(count) = self;
+        *count += 1;
+        Some(*count)
+    }
+}
+
+impl<'a> Visitor<'a> for Counter {
+    fn visit(&self, name: &'a str) -> usize {
+        name.len()
+    }
+}
+
+#[test]
+fn listed_traits() {
+    let taro = &Taro as &dyn Any;
+    assert_eq!(
+        taro.dyncast_to::<dyn Display>().unwrap().to_string(),
+        "taro"
+    );
+    assert_eq!(
+        format!("{:?}", taro.dyncast_to::<dyn Debug>().unwrap()),
+        "Taro"
+    );
+    assert_eq!(
+        taro.dyncast_to::<dyn Boba + Send>().unwrap().flavor(),
+        "taro"
+    );
+    assert!(taro.dyncast_to::<dyn Source<Item = u32>>().is_none());
+
+    let names = dyncast::implementors::<dyn Boba>()
+        .map(|implementor| implementor.type_name())
+        .collect::<Vec<_>>();
+    assert_eq!(names.len(), 2);
+    assert!(names.contains(&type_name::<Taro>()));
+    assert!(names.contains(&type_name::<Counter>()));
+}
+
+#[test]
+fn enums() {
+    let mut counter = Counter::Up(1);
+    let source = (&mut counter as &mut dyn Any)
+        .dyncast_to_mut::<dyn Source<Item = u32>>()
+        .unwrap();
+    assert_eq!(source.next(), Some(2));
+
+    let counter = &counter as &dyn Any;
+    let visitor = counter.dyncast_to::<dyn for<'a> Visitor<'a>>().unwrap();
+    assert_eq!(visitor.visit("boba"), 4);
+    assert!(counter.dyncast_to::<dyn Display>().is_none());
+}